@@ -1,4 +1,5 @@
 use std::{
+    env,
     fmt::Debug,
     fs::File,
     io::{BufReader, Read},
@@ -9,7 +10,11 @@ use memmap2::Mmap;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use serde::{Deserialize, Serialize};
 
-use crate::{emitter::global_emit, utils::get_parallel_files};
+use crate::{
+    drivers::vulnerable::{match_vulnerable_driver, DriverMatch, VulnerableDriver},
+    emitter::global_emit,
+    utils::{get_parallel_files, get_windows_dir},
+};
 
 use super::context::{AnalyzerContext, ItemContext};
 
@@ -109,6 +114,16 @@ impl Analyzer {
         (!items.is_empty()).then(|| AnalyzerContext { items })
     }
 
+    pub fn scan_vulnerable_drivers(
+        dirs: Vec<String>,
+        drivers: &[VulnerableDriver],
+    ) -> Vec<DriverMatch> {
+        driver_scan_files(dirs)
+            .par_iter()
+            .filter_map(|file| match_vulnerable_driver(file, drivers))
+            .collect()
+    }
+
     pub fn generate_context(files: Vec<String>) -> Option<AnalyzerContext> {
         let items: Vec<_> = files
             .par_iter()
@@ -133,3 +148,38 @@ impl Analyzer {
         (!items.is_empty()).then(|| AnalyzerContext { items })
     }
 }
+
+pub fn driver_scan_files(dirs: Vec<String>) -> Vec<String> {
+    dirs.into_iter()
+        .flat_map(get_parallel_files)
+        .filter(|file| {
+            Path::new(file)
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("sys"))
+        })
+        .collect()
+}
+
+pub fn system_driver_dirs() -> Vec<String> {
+    vec![format!("{}\\System32\\drivers", get_windows_dir())]
+}
+
+// драйверы для маппинга обычно лежат рядом с лоадером во временных папках или в профиле,
+// их обход долгий и запускается только отдельной командой
+pub fn user_driver_dirs() -> Vec<String> {
+    let system_drive = env::var("SystemDrive").unwrap_or_else(|_| String::from("C:"));
+
+    let mut dirs = vec![
+        format!("{}\\Temp", get_windows_dir()),
+        format!("{}\\Users", system_drive),
+    ];
+
+    if let Ok(temp) = env::var("TEMP") {
+        // TEMP обычно внутри профиля, который и так обходится целиком
+        if !temp.to_lowercase().starts_with(&dirs[1].to_lowercase()) {
+            dirs.push(temp);
+        }
+    }
+
+    dirs
+}
@@ -1,9 +1,15 @@
 use std::{fs::File, io::Write, path::Path};
 
-use analyzer::Analyzer;
+use analyzer::{user_driver_dirs, Analyzer};
 use context::{load_context, load_context_from_url, AnalyzerContext};
 
-use crate::emitter::global_emit;
+use crate::{
+    defender::quarantine::collect_quarantine_context,
+    drivers::vulnerable::{load_vulnerable_drivers, load_vulnerable_drivers_from_url},
+    emitter::global_emit,
+    mini_dat::{mini_dat::MiniDat, registry_md::wrap_driver_matches},
+    recycle_bin::recycle_bin::collect_recycle_bin_context,
+};
 
 pub mod analyzer;
 pub mod context;
@@ -25,6 +31,33 @@ pub fn generate_context(files: Vec<String>) -> Option<AnalyzerContext> {
     Analyzer::generate_context(files)
 }
 
+#[tauri::command(async)]
+pub async fn scan_vulnerable_drivers(url: Option<String>) -> Vec<MiniDat> {
+    global_emit("task_status_update", "drivers");
+
+    let drivers = match url {
+        Some(url) => load_vulnerable_drivers_from_url(url)
+            .await
+            .unwrap_or_else(load_vulnerable_drivers),
+        None => load_vulnerable_drivers(),
+    };
+
+    wrap_driver_matches(Analyzer::scan_vulnerable_drivers(
+        user_driver_dirs(),
+        &drivers,
+    ))
+}
+
+#[tauri::command(async)]
+pub fn get_quarantine_context() -> Option<AnalyzerContext> {
+    collect_quarantine_context()
+}
+
+#[tauri::command(async)]
+pub fn get_recycle_bin_context() -> Option<AnalyzerContext> {
+    collect_recycle_bin_context()
+}
+
 #[tauri::command(async)]
 pub fn save_context(dir: String, context: AnalyzerContext) {
     match File::create(Path::new(&dir).join("context.json")) {
@@ -46,4 +79,4 @@ pub fn save_context(dir: String, context: AnalyzerContext) {
             }
         }
     }
-}
\ No newline at end of file
+}
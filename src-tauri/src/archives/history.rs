@@ -0,0 +1,251 @@
+use std::fs;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    profiles::user_hive::{collect_user_hives, RegKey, UserHive},
+    regf::hive::{HiveValue, REG_DWORD, REG_QWORD},
+    utils::{is_windows_path, text_bytes_to_string},
+};
+
+pub const ACTION_ARCHIVE: &str = "архив";
+pub const ACTION_EXTRACT: &str = "распаковка в";
+pub const ACTION_FOLDER: &str = "папка";
+
+// (программа, раздел относительно HKCU, значение или все значения раздела, действие)
+const REGISTRY_HISTORY: [(&str, &str, Option<&str>, &str); 10] = [
+    (
+        "7zip",
+        "SOFTWARE\\7-Zip\\Compression",
+        Some("ArcHistory"),
+        ACTION_ARCHIVE,
+    ),
+    (
+        "7zip",
+        "SOFTWARE\\7-Zip\\Extraction",
+        Some("PathHistory"),
+        ACTION_EXTRACT,
+    ),
+    (
+        "7zip",
+        "SOFTWARE\\7-Zip\\FM",
+        Some("FolderHistory"),
+        ACTION_FOLDER,
+    ),
+    (
+        "7zip",
+        "SOFTWARE\\7-Zip\\FM",
+        Some("PathHistory"),
+        ACTION_FOLDER,
+    ),
+    // папки, в которые копировали или распаковывали из File Manager
+    (
+        "7zip",
+        "SOFTWARE\\7-Zip\\FM",
+        Some("CopyHistory"),
+        ACTION_EXTRACT,
+    ),
+    (
+        "winrar",
+        "SOFTWARE\\WinRAR\\ArcHistory",
+        None,
+        ACTION_ARCHIVE,
+    ),
+    (
+        "winrar",
+        "SOFTWARE\\WinRAR\\DialogEditHistory\\ArcName",
+        None,
+        ACTION_ARCHIVE,
+    ),
+    (
+        "winrar",
+        "SOFTWARE\\WinRAR\\DialogEditHistory\\ExtrPath",
+        None,
+        ACTION_EXTRACT,
+    ),
+    (
+        "winzip",
+        "SOFTWARE\\Nico Mak Computing\\WinZip\\filemenu",
+        None,
+        ACTION_ARCHIVE,
+    ),
+    (
+        "winzip",
+        "SOFTWARE\\Nico Mak Computing\\WinZip\\extract",
+        None,
+        ACTION_EXTRACT,
+    ),
+];
+
+// названия разделов и значений bandizip различаются между версиями,
+// поэтому ищем списки недавних файлов по именам
+const BANDIZIP_KEY: &str = "SOFTWARE\\Bandizip";
+const BANDIZIP_MAX_DEPTH: usize = 2;
+const HISTORY_MARKERS: [&str; 4] = ["recent", "history", "mru", "last"];
+const EXTRACT_MARKERS: [&str; 3] = ["extract", "output", "dest"];
+
+const PEAZIP_CONF: &str = "PeaZip\\conf.txt";
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ArchiveHistoryEntry {
+    pub user: String,
+    pub tool: &'static str,
+    pub action: &'static str,
+    pub path: String,
+}
+
+pub fn collect_archive_history(tool: &'static str) -> Vec<ArchiveHistoryEntry> {
+    collect_user_hives()
+        .iter()
+        .flat_map(|user| {
+            let paths = match tool {
+                "bandizip" => read_bandizip(user),
+                "peazip" => read_peazip(user),
+                _ => read_registry_history(user, tool),
+            };
+
+            paths
+                .into_iter()
+                .map(move |(action, path)| ArchiveHistoryEntry {
+                    user: user.profile.name.clone(),
+                    tool,
+                    action,
+                    path,
+                })
+        })
+        .collect()
+}
+
+fn read_registry_history(user: &UserHive, tool: &str) -> Vec<(&'static str, String)> {
+    let mut entries = vec![];
+
+    for (_, path, value, action) in REGISTRY_HISTORY.iter().filter(|(name, ..)| name.eq(&tool)) {
+        let Some(key) = user.open(path) else {
+            continue;
+        };
+
+        let values = match value {
+            Some(name) => key.value(name).into_iter().collect(),
+            None => key.values(),
+        };
+
+        entries.extend(
+            values
+                .iter()
+                .flat_map(value_paths)
+                .map(|path| (*action, path)),
+        );
+    }
+
+    entries
+}
+
+fn read_bandizip(user: &UserHive) -> Vec<(&'static str, String)> {
+    let Some(key) = user.open(BANDIZIP_KEY) else {
+        return vec![];
+    };
+
+    let mut entries = vec![];
+    read_bandizip_key(&key, "", 0, &mut entries);
+
+    entries
+}
+
+fn read_bandizip_key(
+    key: &RegKey,
+    key_name: &str,
+    depth: usize,
+    entries: &mut Vec<(&'static str, String)>,
+) {
+    for value in key.values() {
+        // имя раздела тоже учитываем, значения в списках обычно пронумерованы
+        let name = format!("{}\\{}", key_name, value.name).to_lowercase();
+
+        if !HISTORY_MARKERS.iter().any(|marker| name.contains(marker)) {
+            continue;
+        }
+
+        let action = match EXTRACT_MARKERS.iter().any(|marker| name.contains(marker)) {
+            true => ACTION_EXTRACT,
+            false => ACTION_ARCHIVE,
+        };
+
+        entries.extend(
+            value_paths(&value)
+                .into_iter()
+                .filter(|path| is_windows_path(path))
+                .map(|path| (action, path)),
+        );
+    }
+
+    if depth >= BANDIZIP_MAX_DEPTH {
+        return;
+    }
+
+    for name in key.keys() {
+        if let Some(subkey) = key.open(&name) {
+            read_bandizip_key(&subkey, &name, depth + 1, entries);
+        }
+    }
+}
+
+// установленная версия peazip хранит настройки и историю в conf.txt,
+// история идет отдельными секциями вида [...history...]
+fn read_peazip(user: &UserHive) -> Vec<(&'static str, String)> {
+    let data = match fs::read(user.profile.app_data().join(PEAZIP_CONF)) {
+        Ok(data) => data,
+        Err(e) => {
+            if cfg!(dev) {
+                println!("{e:?}");
+            }
+
+            return vec![];
+        }
+    };
+
+    parse_peazip_conf(&text_bytes_to_string(&data))
+}
+
+pub fn parse_peazip_conf(text: &str) -> Vec<(&'static str, String)> {
+    let mut entries = vec![];
+    let mut section: Option<&'static str> = None;
+
+    for line in text.lines().map(|line| line.trim()) {
+        if line.starts_with('[') && line.ends_with(']') {
+            let name = line.to_lowercase();
+
+            section = HISTORY_MARKERS
+                .iter()
+                .any(|marker| name.contains(marker))
+                .then(
+                    || match EXTRACT_MARKERS.iter().any(|marker| name.contains(marker)) {
+                        true => ACTION_EXTRACT,
+                        false => ACTION_ARCHIVE,
+                    },
+                );
+
+            continue;
+        }
+
+        if let Some(action) = section {
+            if is_windows_path(line) {
+                entries.push((action, line.to_string()));
+            }
+        }
+    }
+
+    entries
+}
+
+// строки хранятся как REG_SZ, а 7-Zip пишет списки в REG_BINARY через \0
+fn value_paths(value: &HiveValue) -> Vec<String> {
+    match value.value_type {
+        REG_DWORD | REG_QWORD => vec![],
+        _ => value
+            .as_multi_string()
+            .into_iter()
+            .map(|path| path.trim().to_string())
+            .filter(|path| !path.is_empty())
+            .collect(),
+    }
+}
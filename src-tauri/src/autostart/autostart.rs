@@ -0,0 +1,282 @@
+use std::{collections::HashSet, fs, path::Path};
+
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use serde::{Deserialize, Serialize};
+use windows_registry::LOCAL_MACHINE;
+
+use crate::{
+    profiles::user_hive::{collect_user_hives, RegKey},
+    utils::{expand_environment_strings, resolve_executable_path, sha256_file},
+};
+
+const RUN_KEYS: [&str; 6] = [
+    "SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Run",
+    "SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\RunOnce",
+    "SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Policies\\Explorer\\Run",
+    "SOFTWARE\\WOW6432Node\\Microsoft\\Windows\\CurrentVersion\\Run",
+    "SOFTWARE\\WOW6432Node\\Microsoft\\Windows\\CurrentVersion\\RunOnce",
+    "SOFTWARE\\WOW6432Node\\Microsoft\\Windows\\CurrentVersion\\Policies\\Explorer\\Run",
+];
+
+const WINLOGON_KEY: &str = "SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion\\Winlogon";
+const WINLOGON_VALUES: [&str; 2] = ["Shell", "Userinit"];
+
+const SHELL_FOLDERS_KEYS: [&str; 2] = [
+    "SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Explorer\\Shell Folders",
+    "SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Explorer\\User Shell Folders",
+];
+
+const SERVICES_KEY: &str = "SYSTEM\\CurrentControlSet\\Services";
+
+// SERVICE_WIN32_OWN_PROCESS | SERVICE_WIN32_SHARE_PROCESS, драйверы собираются отдельно
+const SERVICE_WIN32: u32 = 0x30;
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AutostartEntry {
+    pub source: String,
+    pub name: String,
+    pub command: String,
+    pub path: String,
+    pub exists: bool,
+    pub sha256: Option<String>,
+}
+
+impl AutostartEntry {
+    fn new(source: String, name: String, command: String) -> Self {
+        let path = resolve_executable_path(&command);
+
+        Self {
+            source,
+            name,
+            command,
+            path,
+            exists: false,
+            sha256: None,
+        }
+    }
+
+    fn resolve(mut self) -> Self {
+        let path = Path::new(&self.path);
+        self.exists = path.exists();
+
+        if path.is_file() {
+            self.sha256 = sha256_file(&self.path);
+        }
+
+        self
+    }
+}
+
+pub fn collect_autostart() -> Vec<AutostartEntry> {
+    let mut entries = vec![];
+
+    if let Ok(root) = LOCAL_MACHINE.open("") {
+        entries.extend(read_root("HKLM", &RegKey::Live(root), None));
+    }
+
+    for user in collect_user_hives() {
+        if let Some(root) = user.open("") {
+            let hive = format!("HKU\\{}", user.profile.name);
+            entries.extend(read_root(&hive, &root, Some(&user.profile.path)));
+        }
+    }
+
+    entries.extend(read_services());
+
+    entries
+        .into_par_iter()
+        .map(|entry| entry.resolve())
+        .collect()
+}
+
+fn read_root(hive: &str, root: &RegKey, profile_path: Option<&str>) -> Vec<AutostartEntry> {
+    let mut entries = vec![];
+
+    for path in RUN_KEYS {
+        entries.extend(read_run_key(hive, root, path));
+    }
+
+    entries.extend(read_winlogon(hive, root));
+    entries.extend(read_startup_folders(hive, root, profile_path));
+
+    entries
+}
+
+fn read_run_key(hive: &str, root: &RegKey, path: &str) -> Vec<AutostartEntry> {
+    let Some(key) = root.open(path) else {
+        return vec![];
+    };
+
+    key.values()
+        .into_iter()
+        .filter_map(|value| {
+            let command = value.as_string()?;
+
+            Some(AutostartEntry::new(
+                format!("{}\\{}", hive, path),
+                value.name,
+                command,
+            ))
+        })
+        .collect()
+}
+
+fn read_winlogon(hive: &str, root: &RegKey) -> Vec<AutostartEntry> {
+    let Some(key) = root.open(WINLOGON_KEY) else {
+        return vec![];
+    };
+
+    let mut entries = vec![];
+
+    for name in WINLOGON_VALUES {
+        let Some(value) = key.get_string(name) else {
+            continue;
+        };
+
+        // Userinit может содержать несколько программ через запятую
+        for command in value.split(',').filter(|c| !c.trim().is_empty()) {
+            entries.push(AutostartEntry::new(
+                format!("{}\\{}", hive, WINLOGON_KEY),
+                name.to_string(),
+                command.trim().to_string(),
+            ));
+        }
+    }
+
+    entries
+}
+
+// расположение папки автозагрузки можно переопределить, поэтому выводим и его, и содержимое
+fn read_startup_folders(
+    hive: &str,
+    root: &RegKey,
+    profile_path: Option<&str>,
+) -> Vec<AutostartEntry> {
+    let value_name = match profile_path {
+        None => "Common Startup",
+        Some(_) => "Startup",
+    };
+
+    let mut entries = vec![];
+    let mut folders = HashSet::new();
+
+    for path in SHELL_FOLDERS_KEYS {
+        let Some(key) = root.open(path) else {
+            continue;
+        };
+
+        let Some(mut folder) = key.get_string(value_name) else {
+            continue;
+        };
+
+        // %USERPROFILE% другого пользователя нельзя раскрыть через окружение текущего процесса
+        if let Some(profile_path) = profile_path {
+            folder = replace_user_profile(&folder, profile_path);
+        }
+
+        let folder = expand_environment_strings(&folder);
+        let source = format!("{}\\{}", hive, path);
+
+        // Shell Folders и User Shell Folders обычно указывают на одну и ту же папку
+        if !folders.insert(folder.to_lowercase()) {
+            continue;
+        }
+
+        entries.push(AutostartEntry {
+            source: source.clone(),
+            name: value_name.to_string(),
+            command: folder.clone(),
+            path: folder.clone(),
+            exists: false,
+            sha256: None,
+        });
+
+        let Ok(files) = fs::read_dir(&folder) else {
+            continue;
+        };
+
+        for file in files.filter_map(Result::ok) {
+            let name = file.file_name().to_string_lossy().to_string();
+
+            // desktop.ini есть в каждой папке автозагрузки
+            if name.eq_ignore_ascii_case("desktop.ini") {
+                continue;
+            }
+
+            let path = file.path().to_string_lossy().to_string();
+
+            entries.push(AutostartEntry {
+                source: folder.clone(),
+                name,
+                command: path.clone(),
+                path,
+                exists: false,
+                sha256: None,
+            });
+        }
+    }
+
+    entries
+}
+
+fn read_services() -> Vec<AutostartEntry> {
+    let Ok(services) = LOCAL_MACHINE.open(SERVICES_KEY) else {
+        return vec![];
+    };
+
+    let Ok(names) = services.keys() else {
+        return vec![];
+    };
+
+    names
+        .filter_map(|name| {
+            let service = services.open(&name).ok()?;
+            let service_type = service.get_u32("Type").ok()?;
+
+            if service_type & SERVICE_WIN32 == 0 {
+                return None;
+            }
+
+            let command = service.get_string("ImagePath").ok()?;
+            let start = service.get_u32("Start").unwrap_or(u32::MAX);
+
+            Some(AutostartEntry::new(
+                format!("HKLM\\{}\\{}", SERVICES_KEY, name),
+                format!("{} ({})", name, start_type_name(start)),
+                command,
+            ))
+        })
+        .collect()
+}
+
+pub fn start_type_name(start: u32) -> &'static str {
+    match start {
+        0 => "boot",
+        1 => "system",
+        2 => "auto",
+        3 => "manual",
+        4 => "disabled",
+        _ => "unknown",
+    }
+}
+
+fn replace_user_profile(value: &str, profile_path: &str) -> String {
+    const USER_PROFILE: &str = "%userprofile%";
+
+    // ищем по исходной строке, индекс в lowercase копии может не совпасть
+    let index = value.char_indices().map(|(i, _)| i).find(|&i| {
+        value
+            .get(i..i + USER_PROFILE.len())
+            .is_some_and(|s| s.eq_ignore_ascii_case(USER_PROFILE))
+    });
+
+    match index {
+        Some(index) => format!(
+            "{}{}{}",
+            &value[..index],
+            profile_path,
+            &value[index + USER_PROFILE.len()..]
+        ),
+        None => value.to_string(),
+    }
+}
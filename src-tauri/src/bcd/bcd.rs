@@ -0,0 +1,232 @@
+use std::{collections::HashMap, io::Read};
+
+use serde::{Deserialize, Serialize};
+use windows_registry::{Key, LOCAL_MACHINE};
+
+use crate::{
+    mini_dat::registry_md::bytes_to_vec_u8,
+    regf::hive::{Hive, HiveKey},
+    utils::utf16_bytes_to_string,
+};
+
+// загруженное хранилище всегда доступно через реестр, сам файл заблокирован системой
+const BCD_KEY: &str = "BCD00000000";
+const BCD_FILES: [&str; 2] = ["C:\\Boot\\BCD", "C:\\EFI\\Microsoft\\Boot\\BCD"];
+
+const OBJECT_TYPE_OS_LOADER: u32 = 0x10200003;
+const BOOT_MANAGER_ID: &str = "{9dea862c-5cdd-4e70-acc1-f32b344d4795}";
+
+// https://learn.microsoft.com/en-us/previous-versions/windows/desktop/bcd/bcdosloaderelementtypes
+const ELEMENT_DESCRIPTION: u32 = 0x12000004;
+const ELEMENT_APPLICATION_PATH: u32 = 0x12000002;
+const ELEMENT_LOAD_OPTIONS: u32 = 0x12000030;
+const ELEMENT_DEBUGGER_ENABLED: u32 = 0x16000010;
+const ELEMENT_DISABLE_INTEGRITY_CHECKS: u32 = 0x16000048;
+const ELEMENT_ALLOW_PRERELEASE_SIGNATURES: u32 = 0x16000049;
+const ELEMENT_SYSTEM_ROOT: u32 = 0x22000002;
+const ELEMENT_DEFAULT_OBJECT: u32 = 0x23000003;
+const ELEMENT_DISPLAY_ORDER: u32 = 0x24000001;
+const ELEMENT_NX_POLICY: u32 = 0x25000020;
+const ELEMENT_HYPERVISOR_LAUNCH_TYPE: u32 = 0x250000f0;
+const ELEMENT_WINPE: u32 = 0x26000022;
+const ELEMENT_KERNEL_DEBUGGER_ENABLED: u32 = 0x260000a0;
+
+#[derive(Debug, Clone, Default)]
+pub struct BcdObject {
+    pub id: String,
+    pub object_type: u32,
+    // сырые данные элементов, формат определяется битами 24-27 идентификатора
+    pub elements: HashMap<u32, Vec<u8>>,
+}
+
+impl BcdObject {
+    fn string(&self, element: u32) -> Option<String> {
+        self.elements
+            .get(&element)
+            .map(|d| utf16_bytes_to_string(d))
+    }
+
+    fn strings(&self, element: u32) -> Vec<String> {
+        self.string(element)
+            .map(|s| {
+                s.split('\0')
+                    .filter(|s| !s.is_empty())
+                    .map(|s| s.to_lowercase())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn boolean(&self, element: u32) -> Option<bool> {
+        self.elements
+            .get(&element)
+            .and_then(|d| d.first())
+            .map(|b| *b != 0)
+    }
+
+    fn integer(&self, element: u32) -> Option<u64> {
+        let data = self.elements.get(&element)?;
+        let mut bytes = [0u8; 8];
+        let size = data.len().min(8);
+        bytes[..size].copy_from_slice(&data[..size]);
+
+        Some(u64::from_le_bytes(bytes))
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BootEntry {
+    pub id: String,
+    pub description: String,
+    pub path: String,
+    pub system_root: String,
+    pub load_options: String,
+    pub test_signing: bool,
+    pub no_integrity_checks: bool,
+    pub debug: bool,
+    pub boot_debug: bool,
+    pub winpe: bool,
+    pub hypervisor_launch_type: Option<u64>,
+    pub nx_policy: Option<u64>,
+    pub is_default: bool,
+    pub in_display_order: bool,
+    pub source: String,
+}
+
+pub fn collect_boot_entries() -> Vec<BootEntry> {
+    let objects = match read_from_registry() {
+        Some(objects) => Some((objects, format!("HKLM\\{}", BCD_KEY))),
+        None => BCD_FILES
+            .iter()
+            .find_map(|path| read_from_hive(path).map(|objects| (objects, path.to_string()))),
+    };
+
+    let Some((objects, source)) = objects else {
+        return vec![];
+    };
+
+    let boot_manager = objects
+        .iter()
+        .find(|o| o.id.eq_ignore_ascii_case(BOOT_MANAGER_ID));
+    let default = boot_manager
+        .and_then(|o| o.string(ELEMENT_DEFAULT_OBJECT))
+        .unwrap_or_default()
+        .to_lowercase();
+    let display_order = boot_manager
+        .map(|o| o.strings(ELEMENT_DISPLAY_ORDER))
+        .unwrap_or_default();
+
+    objects
+        .iter()
+        .filter(|object| object.object_type == OBJECT_TYPE_OS_LOADER)
+        .map(|object| {
+            let id = object.id.to_lowercase();
+
+            BootEntry {
+                description: object.string(ELEMENT_DESCRIPTION).unwrap_or_default(),
+                path: object.string(ELEMENT_APPLICATION_PATH).unwrap_or_default(),
+                system_root: object.string(ELEMENT_SYSTEM_ROOT).unwrap_or_default(),
+                load_options: object.string(ELEMENT_LOAD_OPTIONS).unwrap_or_default(),
+                test_signing: object
+                    .boolean(ELEMENT_ALLOW_PRERELEASE_SIGNATURES)
+                    .unwrap_or(false),
+                no_integrity_checks: object
+                    .boolean(ELEMENT_DISABLE_INTEGRITY_CHECKS)
+                    .unwrap_or(false),
+                debug: object
+                    .boolean(ELEMENT_KERNEL_DEBUGGER_ENABLED)
+                    .unwrap_or(false),
+                boot_debug: object.boolean(ELEMENT_DEBUGGER_ENABLED).unwrap_or(false),
+                winpe: object.boolean(ELEMENT_WINPE).unwrap_or(false),
+                hypervisor_launch_type: object.integer(ELEMENT_HYPERVISOR_LAUNCH_TYPE),
+                nx_policy: object.integer(ELEMENT_NX_POLICY),
+                is_default: id.eq(&default),
+                in_display_order: display_order.contains(&id),
+                id: object.id.clone(),
+                source: source.clone(),
+            }
+        })
+        .collect()
+}
+
+// Objects\{id}\Description\Type и Objects\{id}\Elements\{element}\Element
+fn read_from_registry() -> Option<Vec<BcdObject>> {
+    let objects = LOCAL_MACHINE.open(format!("{}\\Objects", BCD_KEY)).ok()?;
+
+    Some(
+        objects
+            .keys()
+            .ok()?
+            .filter_map(|id| {
+                let object = objects.open(&id).ok()?;
+                Some(BcdObject {
+                    object_type: object
+                        .open("Description")
+                        .and_then(|d| d.get_u32("Type"))
+                        .unwrap_or(0),
+                    elements: read_registry_elements(&object),
+                    id,
+                })
+            })
+            .collect(),
+    )
+}
+
+fn read_registry_elements(object: &Key) -> HashMap<u32, Vec<u8>> {
+    let mut elements = HashMap::new();
+
+    let Ok(key) = object.open("Elements") else {
+        return elements;
+    };
+
+    let Ok(names) = key.keys() else {
+        return elements;
+    };
+
+    for name in names {
+        let (Ok(element), Ok(id)) = (key.open(&name), u32::from_str_radix(&name, 16)) else {
+            continue;
+        };
+
+        if let Ok(value) = element.get_value("Element") {
+            elements.insert(id, bytes_to_vec_u8(value.bytes()));
+        }
+    }
+
+    elements
+}
+
+fn read_from_hive(path: &str) -> Option<Vec<BcdObject>> {
+    let hive = Hive::open(path)?;
+    let objects = hive.open_key("Objects")?;
+
+    Some(
+        objects
+            .keys()
+            .iter()
+            .map(|object| BcdObject {
+                id: object.name.clone(),
+                object_type: object
+                    .open("Description")
+                    .and_then(|d| d.value("Type"))
+                    .and_then(|v| v.as_u32())
+                    .unwrap_or(0),
+                elements: read_hive_elements(object),
+            })
+            .collect(),
+    )
+}
+
+fn read_hive_elements(object: &HiveKey) -> HashMap<u32, Vec<u8>> {
+    let Some(key) = object.key("Elements") else {
+        return HashMap::new();
+    };
+
+    key.keys()
+        .iter()
+        .filter_map(|element| {
+            let id = u32::from_str_radix(&element.name, 16).ok()?;
+            Some((id, element.value("Element")?.data))
+        })
+        .collect()
+}
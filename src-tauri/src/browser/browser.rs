@@ -2,6 +2,8 @@ use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
 
+use crate::profiles::profiles::collect_user_profiles;
+
 use super::driver::Driver;
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -28,31 +30,56 @@ impl Browser {
 }
 
 pub fn get_browsers() -> Vec<Browser> {
-    let app_data_dir = PathBuf::from(format!("C:\\Users\\{}\\AppData", whoami::username()));
+    let profiles = collect_user_profiles();
+
+    if profiles.is_empty() {
+        return get_profile_browsers(
+            PathBuf::from(format!("C:\\Users\\{}\\AppData", whoami::username())),
+            "",
+        );
+    }
+
+    // браузеры текущего пользователя сохраняют прежние id, у остальных добавляется имя профиля
+    profiles
+        .iter()
+        .flat_map(|profile| {
+            let suffix = match profile.current {
+                true => String::new(),
+                false => format!(" ({})", profile.name),
+            };
+
+            get_profile_browsers(Path::new(&profile.path).join("AppData"), &suffix)
+                .into_iter()
+                .filter(|browser| profile.current || browser.is_present())
+                .collect::<Vec<Browser>>()
+        })
+        .collect()
+}
 
+fn get_profile_browsers(app_data_dir: PathBuf, suffix: &str) -> Vec<Browser> {
     return vec![
         Browser::new(
-            String::from("chrome"),
+            format!("chrome{}", suffix),
             app_data_dir.join("Local\\Google\\Chrome\\User Data\\Default"),
             Driver::BLINK,
         ),
         Browser::new(
-            String::from("yandex"),
+            format!("yandex{}", suffix),
             app_data_dir.join("Local\\Yandex\\YandexBrowser\\User Data\\Default"),
             Driver::BLINK,
         ),
         Browser::new(
-            String::from("edge"),
+            format!("edge{}", suffix),
             app_data_dir.join("Local\\Microsoft\\Edge\\User Data\\Default"),
             Driver::BLINK,
         ),
         Browser::new(
-            String::from("opera"),
+            format!("opera{}", suffix),
             app_data_dir.join("Roaming\\Opera Software\\Opera Stable\\Default"),
             Driver::BLINK,
         ),
         Browser::new(
-            String::from("brave"),
+            format!("brave{}", suffix),
             app_data_dir.join("Local\\BraveSoftware\\Brave-Browser\\User Data\\Default"),
             Driver::BLINK,
         ),
@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+
+use super::{detection_history::read_detection_history, mplog::read_mplogs};
+
+pub const DEFENDER_DIR: &str = "C:\\ProgramData\\Microsoft\\Windows Defender";
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DefenderDetection {
+    pub timestamp: i64,
+    pub source: &'static str,
+    pub threat: String,
+    pub path: String,
+    pub action: String,
+}
+
+pub fn collect_detections() -> Vec<DefenderDetection> {
+    let mut response = read_detection_history(DEFENDER_DIR);
+    response.extend(read_mplogs(DEFENDER_DIR));
+
+    response.sort_by_key(|detection| detection.timestamp);
+    response
+}
+
+// имена угроз Defender имеют вид Type:Platform/Family.Variant, например HackTool:Win32/Cheat.A
+pub fn is_threat_name(value: &str) -> bool {
+    let Some((kind, rest)) = value.split_once(':') else {
+        return false;
+    };
+
+    let Some((platform, family)) = rest.split_once('/') else {
+        return false;
+    };
+
+    !kind.is_empty()
+        && kind.chars().all(|c| c.is_ascii_alphanumeric())
+        && !platform.is_empty()
+        && platform.chars().all(|c| c.is_ascii_alphanumeric())
+        && !family.is_empty()
+        && !family.contains(char::is_whitespace)
+}
@@ -0,0 +1,235 @@
+use std::{
+    fs,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use jwalk::WalkDir;
+
+use crate::utils::{filetime_to_unix, unix_to_filetime, utf16_bytes_to_string};
+
+use super::defender::{is_threat_name, DefenderDetection};
+
+const DETECTION_HISTORY_DIR: &str = "Scans\\History\\Service\\DetectionHistory";
+
+// префиксы ресурсов, к которым относится обнаружение
+const RESOURCE_PREFIXES: [&str; 4] = ["file:_", "containerfile:_", "process:_", "regkey:_"];
+
+const MIN_STRING_LENGTH: usize = 4;
+
+// значение идет сразу за именем поля, поэтому ищем его в небольшом окне после строки
+const VALUE_WINDOW: usize = 48;
+
+// 01.01.2001, все что раньше не может быть временем обнаружения
+const MIN_FILETIME: i64 = 126_227_808_000_000_000;
+
+// https://learn.microsoft.com/en-us/previous-versions/windows/desktop/defender/msft-mpthreatdetection
+const THREAT_STATUSES: [(u32, &str); 11] = [
+    (1, "обнаружено"),
+    (2, "обезврежено"),
+    (3, "помещено в карантин"),
+    (4, "удалено"),
+    (5, "разрешено пользователем"),
+    (6, "заблокировано"),
+    (102, "ошибка карантина"),
+    (103, "ошибка удаления"),
+    (104, "ошибка разрешения"),
+    (105, "отменено"),
+    (107, "ошибка блокировки"),
+];
+
+// файлы DetectionHistory не документированы, поэтому достаем из них utf-16 строки,
+// выбираем среди них имя угрозы и затронутые ресурсы, а время и статус читаем за именами полей
+pub fn read_detection_history(defender_dir: &str) -> Vec<DefenderDetection> {
+    WalkDir::new(Path::new(defender_dir).join(DETECTION_HISTORY_DIR))
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type.is_file())
+        .filter_map(|entry| read_detection_file(&entry.path()))
+        .flatten()
+        .collect()
+}
+
+fn read_detection_file(path: &Path) -> Option<Vec<DefenderDetection>> {
+    let data = match fs::read(path) {
+        Ok(data) => data,
+        Err(e) => {
+            if cfg!(dev) {
+                println!("{e:?}");
+            }
+
+            return None;
+        }
+    };
+
+    let fields = extract_strings(&data);
+    let strings: Vec<&String> = fields.iter().map(|(_, string)| string).collect();
+    let threat = strings.iter().find(|s| is_threat_name(s))?.to_string();
+
+    let timestamp = match read_detection_time(&data, &fields) {
+        Some(filetime) => filetime_to_unix(filetime),
+        None => fs::metadata(path)
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0),
+    };
+
+    let action = read_threat_status(&data, &fields).unwrap_or_default();
+
+    let mut resources: Vec<String> = vec![];
+
+    for string in strings {
+        for prefix in RESOURCE_PREFIXES {
+            if let Some(resource) = string.strip_prefix(prefix) {
+                if !resources.iter().any(|r| r.eq(resource)) {
+                    resources.push(resource.to_string());
+                }
+            }
+        }
+    }
+
+    if resources.is_empty() {
+        resources.push(String::new());
+    }
+
+    Some(
+        resources
+            .into_iter()
+            .map(|resource| DefenderDetection {
+                timestamp,
+                source: "DetectionHistory",
+                threat: threat.clone(),
+                path: resource,
+                action: action.clone(),
+            })
+            .collect(),
+    )
+}
+
+// время обнаружения хранится как FILETIME за полем вида ThreatTrackingStartTime,
+// другие метки в файле к обнаружению не относятся, поэтому дальше поля не ищем
+pub fn read_detection_time(data: &[u8], fields: &[(usize, String)]) -> Option<i64> {
+    fields
+        .iter()
+        .filter(|(_, key)| key.ends_with("Time"))
+        .find_map(|(end, _)| find_filetime(data, *end, (*end + VALUE_WINDOW).min(data.len())))
+}
+
+fn find_filetime(data: &[u8], start: usize, end: usize) -> Option<i64> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+    let max_filetime = unix_to_filetime(now + 86_400);
+
+    // FILETIME в файле выровнен на 8 байт
+    (start.next_multiple_of(8)..end.saturating_sub(7))
+        .step_by(8)
+        .map(|offset| i64::from_le_bytes(data[offset..offset + 8].try_into().unwrap()))
+        .find(|filetime| (MIN_FILETIME..max_filetime).contains(filetime))
+}
+
+// статус угрозы (ThreatStatusID) записан числом за полем со Status в имени
+pub fn read_threat_status(data: &[u8], fields: &[(usize, String)]) -> Option<String> {
+    fields
+        .iter()
+        .filter(|(_, key)| key.contains("Status"))
+        .find_map(|(start, _)| {
+            let end = (*start + VALUE_WINDOW).min(data.len());
+
+            let status = (start.next_multiple_of(4)..end.saturating_sub(3))
+                .step_by(4)
+                .map(|offset| u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap()))
+                .find(|value| *value != 0)?;
+
+            THREAT_STATUSES
+                .iter()
+                .find(|(id, _)| *id == status)
+                .map(|(_, name)| name.to_string())
+        })
+}
+
+// строки вместе со смещением конца строки, за которым идет значение поля
+pub fn extract_strings(data: &[u8]) -> Vec<(usize, String)> {
+    let mut strings = vec![];
+    let mut start = 0;
+    let mut offset = 0;
+
+    while offset + 1 < data.len() {
+        let c = u16::from_le_bytes([data[offset], data[offset + 1]]);
+
+        if c == 0 || (c < 0x20 && c != 0x09) {
+            if (offset - start) / 2 >= MIN_STRING_LENGTH {
+                strings.push((offset, utf16_bytes_to_string(&data[start..offset])));
+            }
+
+            start = offset + 2;
+        }
+
+        offset += 2;
+    }
+
+    strings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utf16(value: &str) -> Vec<u8> {
+        value
+            .encode_utf16()
+            .chain([0])
+            .flat_map(u16::to_le_bytes)
+            .collect()
+    }
+
+    // имя поля, выравнивание до 8 байт и значение
+    fn field(data: &mut Vec<u8>, name: &str, value: &[u8]) {
+        data.extend(utf16(name));
+        data.resize(data.len().next_multiple_of(8), 0);
+        data.extend_from_slice(value);
+    }
+
+    #[test]
+    fn reads_time_and_status_after_fields() {
+        let filetime = unix_to_filetime(1_704_103_200);
+
+        let mut data = vec![0; 8];
+        data.extend(utf16("HackTool:Win32/Cheat.A"));
+        data.extend(utf16("file:_C:\\cheat.exe"));
+        field(
+            &mut data,
+            "ThreatTrackingStartTime",
+            &filetime.to_le_bytes(),
+        );
+        field(&mut data, "ThreatStatusID", &3u32.to_le_bytes());
+
+        let fields = extract_strings(&data);
+
+        assert_eq!(read_detection_time(&data, &fields), Some(filetime));
+        assert_eq!(
+            read_threat_status(&data, &fields).as_deref(),
+            Some("помещено в карантин")
+        );
+    }
+
+    #[test]
+    fn ignores_implausible_times() {
+        let mut data = vec![0; 8];
+        field(&mut data, "ThreatTrackingStartTime", &1u64.to_le_bytes());
+
+        assert_eq!(read_detection_time(&data, &extract_strings(&data)), None);
+    }
+
+    #[test]
+    fn ignores_times_outside_fields() {
+        let mut data = vec![0; 8];
+        data.extend(unix_to_filetime(1_704_103_200).to_le_bytes());
+        field(&mut data, "HackTool:Win32/Cheat.A", &[0; 8]);
+
+        assert_eq!(read_detection_time(&data, &extract_strings(&data)), None);
+    }
+}
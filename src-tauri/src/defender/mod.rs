@@ -0,0 +1,5 @@
+pub mod defender;
+pub mod detection_history;
+pub mod mplog;
+pub mod quarantine;
+pub mod settings;
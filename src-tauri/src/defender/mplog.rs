@@ -0,0 +1,109 @@
+use std::{fs, path::Path};
+
+use chrono::DateTime;
+
+use crate::utils::text_bytes_to_string;
+
+use super::defender::{is_threat_name, DefenderDetection};
+
+const SUPPORT_DIR: &str = "Support";
+
+// события журнала, в которых фигурирует угроза
+const DETECTION_EVENTS: [(&str, &str); 5] = [
+    ("DETECTIONEVENT", "обнаружено"),
+    ("DETECTION_ADD", "добавлено в историю"),
+    ("DETECTION_DELETE", "удалено из истории"),
+    ("DETECTION_CLEANED", "обезврежено"),
+    ("DETECTION_ALLOWED", "разрешено пользователем"),
+];
+
+pub fn read_mplogs(defender_dir: &str) -> Vec<DefenderDetection> {
+    let entries = match fs::read_dir(Path::new(defender_dir).join(SUPPORT_DIR)) {
+        Ok(entries) => entries,
+        Err(e) => {
+            if cfg!(dev) {
+                println!("{e:?}");
+            }
+
+            return vec![];
+        }
+    };
+
+    entries
+        .filter_map(Result::ok)
+        .filter(|entry| {
+            let name = entry.file_name().to_string_lossy().to_lowercase();
+            name.starts_with("mplog-") && name.ends_with(".log")
+        })
+        .filter_map(|entry| fs::read(entry.path()).ok())
+        .flat_map(|data| parse_mplog(&text_bytes_to_string(&data)))
+        .collect()
+}
+
+// строки вида 2024-01-01T10:00:00.000Z DETECTION_ADD#2 HackTool:Win32/Cheat.A file:C:\cheat.exe
+fn parse_mplog(text: &str) -> Vec<DefenderDetection> {
+    let mut detections = vec![];
+
+    for line in text.lines() {
+        let mut tokens = line.split_whitespace();
+
+        let (Some(time), Some(event)) = (tokens.next(), tokens.next()) else {
+            continue;
+        };
+
+        let event = event.split('#').next().unwrap_or(event);
+
+        let Some((_, action)) = DETECTION_EVENTS.iter().find(|(name, _)| event.eq(*name)) else {
+            continue;
+        };
+
+        let Ok(time) = DateTime::parse_from_rfc3339(time) else {
+            continue;
+        };
+
+        let rest: Vec<&str> = tokens.collect();
+
+        let Some(threat) = rest.iter().find(|t| is_threat_name(t)) else {
+            continue;
+        };
+
+        // путь может содержать пробелы, поэтому берем весь хвост после префикса ресурса
+        let rest = rest.join(" ");
+        let path = rest
+            .split_once("file:")
+            .map(|(_, path)| path.trim_end_matches(';').trim().to_string())
+            .unwrap_or_default();
+
+        detections.push(DefenderDetection {
+            timestamp: time.timestamp(),
+            source: "MPLog",
+            threat: threat.to_string(),
+            path,
+            action: action.to_string(),
+        });
+    }
+
+    detections
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_detection_lines() {
+        let text = "2024-01-01T10:00:00.000Z DETECTION_ADD#2 HackTool:Win32/Cheat.A file:C:\\Program Files\\cheat.exe;\n\
+                    2024-01-01T10:00:01.000Z SIGNATURE_UPDATE nothing here\n\
+                    2024-01-01T10:00:02.000Z DETECTION_CLEANED Trojan:Win32/Wacatac.B process:pid:10";
+
+        let detections = parse_mplog(text);
+
+        assert_eq!(detections.len(), 2);
+        assert_eq!(detections[0].timestamp, 1704103200);
+        assert_eq!(detections[0].threat, "HackTool:Win32/Cheat.A");
+        assert_eq!(detections[0].path, "C:\\Program Files\\cheat.exe");
+        assert_eq!(detections[0].action, "добавлено в историю");
+        assert_eq!(detections[1].path, "");
+        assert_eq!(detections[1].action, "обезврежено");
+    }
+}
@@ -0,0 +1,276 @@
+use std::{fs, path::Path};
+
+use jwalk::WalkDir;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    analyzer::context::{AnalyzerContext, ItemContext},
+    utils::utf16_bytes_to_string,
+};
+
+use super::defender::DEFENDER_DIR;
+
+const ENTRIES_DIR: &str = "Quarantine\\Entries";
+const RESOURCE_DATA_DIR: &str = "Quarantine\\ResourceData";
+
+const ENTRY_HEADER_SIZE: usize = 0x3c;
+const SHA1_SIZE: usize = 20;
+
+// ключ rc4, которым Защитник шифрует файлы карантина
+// https://github.com/knez/defender-dump
+const RC4_KEY: [u8; 256] = [
+    0x1E, 0x87, 0x78, 0x1B, 0x8D, 0xBA, 0xA8, 0x44, 0xCE, 0x69, 0x70, 0x2C, 0x0C, 0x78, 0xB7, 0x86,
+    0xA3, 0xF6, 0x23, 0xB7, 0x38, 0xF5, 0xED, 0xF9, 0xAF, 0x83, 0x53, 0x0F, 0xB3, 0xFC, 0x54, 0xFA,
+    0xA2, 0x1E, 0xB9, 0xCF, 0x13, 0x31, 0xFD, 0x0F, 0x0D, 0xA9, 0x54, 0xF6, 0x87, 0xCB, 0x9E, 0x18,
+    0x27, 0x96, 0x97, 0x90, 0x0E, 0x53, 0xFB, 0x31, 0x7C, 0x9C, 0xBC, 0xE4, 0x8E, 0x23, 0xD0, 0x53,
+    0x71, 0xEC, 0xC1, 0x59, 0x51, 0xB8, 0xF3, 0x64, 0x9D, 0x7C, 0xA3, 0x3E, 0xD6, 0x8D, 0xC9, 0x04,
+    0x7E, 0x82, 0xC9, 0xBA, 0xAD, 0x97, 0x99, 0xD0, 0xD4, 0x58, 0xCB, 0x84, 0x7C, 0xA9, 0xFF, 0xBE,
+    0x3C, 0x8A, 0x77, 0x52, 0x33, 0x55, 0x7D, 0xDE, 0x13, 0xA8, 0xB1, 0x40, 0x87, 0xCC, 0x1B, 0xC8,
+    0xF1, 0x0F, 0x6E, 0xCD, 0xD0, 0x83, 0xA9, 0x59, 0xCF, 0xF8, 0x4A, 0x9D, 0x1D, 0x50, 0x75, 0x5E,
+    0x3E, 0x19, 0x18, 0x18, 0xAF, 0x23, 0xE2, 0x29, 0x35, 0x58, 0x76, 0x6D, 0x2C, 0x07, 0xE2, 0x57,
+    0x12, 0xB2, 0xCA, 0x0B, 0x53, 0x5E, 0xD8, 0xF6, 0xC5, 0x6C, 0xE7, 0x3D, 0x24, 0xBD, 0xD0, 0x29,
+    0x17, 0x71, 0x86, 0x1A, 0x54, 0xB4, 0xC2, 0x85, 0xA9, 0xA3, 0xDB, 0x7A, 0xCA, 0x6D, 0x22, 0x4A,
+    0xEA, 0xCD, 0x62, 0x1D, 0xB9, 0xF2, 0xA2, 0x2E, 0xD1, 0xE9, 0xE1, 0x1D, 0x75, 0xBE, 0xD7, 0xDC,
+    0x0E, 0xCB, 0x0A, 0x8E, 0x68, 0xA2, 0xFF, 0x12, 0x63, 0x40, 0x8D, 0xC8, 0x08, 0xDF, 0xFD, 0x16,
+    0x4B, 0x11, 0x67, 0x74, 0xCD, 0x0B, 0x9B, 0x8D, 0x05, 0x41, 0x1E, 0xD6, 0x26, 0x2E, 0x42, 0x9B,
+    0xA4, 0x95, 0x67, 0x6B, 0x83, 0x98, 0xDB, 0x2F, 0x35, 0xD3, 0xC1, 0xB9, 0xCE, 0xD5, 0x26, 0x36,
+    0xF2, 0x76, 0x5E, 0x1A, 0x95, 0xCB, 0x7C, 0xA4, 0xC3, 0xDD, 0xAB, 0xDD, 0xBF, 0xF3, 0x82, 0x53,
+];
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct QuarantineItem {
+    pub timestamp: i64,
+    pub threat: String,
+    pub path: String,
+    pub size: u64,
+    pub crc32: u32,
+    pub sha1: String,
+    pub sha256: String,
+}
+
+// запись из Entries: ресурс угрозы вместе с сырыми полями, среди которых лежит sha1 файла
+struct QuarantineEntry {
+    timestamp: i64,
+    threat: String,
+    path: String,
+    fields: Vec<u8>,
+}
+
+pub fn collect_quarantine() -> Vec<QuarantineItem> {
+    read_quarantine(DEFENDER_DIR)
+}
+
+pub fn collect_quarantine_context() -> Option<AnalyzerContext> {
+    let items: Vec<ItemContext> = collect_quarantine()
+        .into_iter()
+        .map(|item| ItemContext {
+            name: Path::new(&item.path)
+                .file_name()
+                .and_then(|n| n.to_str())
+                .map(|s| s.to_owned())
+                .unwrap_or_else(|| item.sha1.clone()),
+            path: item.path,
+            size: item.size,
+            crc32: item.crc32,
+        })
+        .collect();
+
+    (!items.is_empty()).then_some(AnalyzerContext { items })
+}
+
+pub fn read_quarantine(defender_dir: &str) -> Vec<QuarantineItem> {
+    let dir = Path::new(defender_dir);
+
+    let entries: Vec<QuarantineEntry> = WalkDir::new(dir.join(ENTRIES_DIR))
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type.is_file())
+        .filter_map(|entry| fs::read(entry.path()).ok())
+        .filter_map(|data| read_entry(&data))
+        .flatten()
+        .collect();
+
+    // файлы в ResourceData названы по sha1 исходного содержимого
+    WalkDir::new(dir.join(RESOURCE_DATA_DIR))
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type.is_file())
+        .filter_map(|entry| {
+            let sha1 = entry.file_name().to_string_lossy().to_lowercase();
+            let sha1_bytes = hex::decode(&sha1).ok().filter(|b| b.len() == SHA1_SIZE)?;
+            let payload = read_resource(&fs::read(entry.path()).ok()?)?;
+
+            let entry = entries.iter().find(|e| {
+                e.fields
+                    .windows(SHA1_SIZE)
+                    .any(|w| w.eq(sha1_bytes.as_slice()))
+            });
+
+            Some(QuarantineItem {
+                timestamp: entry.map(|e| e.timestamp).unwrap_or(0),
+                threat: entry.map(|e| e.threat.clone()).unwrap_or_default(),
+                path: entry.map(|e| e.path.clone()).unwrap_or_default(),
+                size: payload.len() as u64,
+                crc32: crc32fast::hash(&payload),
+                sha1,
+                sha256: hex::encode(Sha256::digest(&payload)),
+            })
+        })
+        .collect()
+}
+
+// заголовок, описание угрозы и список ресурсов шифруются по отдельности
+fn read_entry(data: &[u8]) -> Option<Vec<QuarantineEntry>> {
+    let header = rc4(data.get(..ENTRY_HEADER_SIZE)?);
+    let threat_size = u32_at(&header, 0x28)? as usize;
+    let resources_size = u32_at(&header, 0x2c)? as usize;
+
+    let threat_end = ENTRY_HEADER_SIZE + threat_size;
+    let threat = rc4(data.get(ENTRY_HEADER_SIZE..threat_end)?);
+    let resources = rc4(data.get(threat_end..threat_end + resources_size)?);
+
+    let timestamp = u64_at(&threat, 0x20)? as i64;
+    let threat_name = threat
+        .get(0x34..)?
+        .split(|b| *b == 0)
+        .next()
+        .map(|name| String::from_utf8_lossy(name).to_string())
+        .unwrap_or_default();
+
+    let count = u32_at(&resources, 0)? as usize;
+    let offsets: Vec<usize> = (0..count)
+        .filter_map(|i| u32_at(&resources, 4 + i * 4).map(|o| o as usize))
+        .collect();
+
+    let mut entries = vec![];
+
+    for (index, offset) in offsets.iter().enumerate() {
+        let end = offsets
+            .get(index + 1)
+            .copied()
+            .unwrap_or(resources.len())
+            .min(resources.len());
+
+        let Some(fields) = resources.get(*offset..end) else {
+            continue;
+        };
+
+        let path_size = fields
+            .chunks_exact(2)
+            .position(|c| c == [0, 0])
+            .unwrap_or(fields.len() / 2)
+            * 2;
+
+        let path = utf16_bytes_to_string(&fields[..path_size]);
+
+        entries.push(QuarantineEntry {
+            timestamp,
+            threat: threat_name.clone(),
+            path: path.trim_start_matches("\\\\?\\").to_string(),
+            fields: fields.to_vec(),
+        });
+    }
+
+    Some(entries)
+}
+
+// после расшифровки идет заголовок с дескриптором безопасности, а за ним исходный файл
+fn read_resource(data: &[u8]) -> Option<Vec<u8>> {
+    let data = rc4(data);
+    let descriptor_size = u32_at(&data, 0x8)? as usize;
+    let header_size = 0x28 + descriptor_size;
+    let size = u64_at(&data, descriptor_size + 0x1c)? as usize;
+
+    Some(
+        data.get(header_size..header_size.checked_add(size)?)?
+            .to_vec(),
+    )
+}
+
+fn rc4(data: &[u8]) -> Vec<u8> {
+    let mut state: Vec<u8> = (0..=255).collect();
+    let mut j: u8 = 0;
+
+    for i in 0..256 {
+        j = j.wrapping_add(state[i]).wrapping_add(RC4_KEY[i]);
+        state.swap(i, j as usize);
+    }
+
+    let (mut i, mut j) = (0u8, 0u8);
+
+    data.iter()
+        .map(|b| {
+            i = i.wrapping_add(1);
+            j = j.wrapping_add(state[i as usize]);
+            state.swap(i as usize, j as usize);
+            b ^ state[state[i as usize].wrapping_add(state[j as usize]) as usize]
+        })
+        .collect()
+}
+
+fn u32_at(data: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(
+        data.get(offset..offset + 4)?.try_into().ok()?,
+    ))
+}
+
+fn u64_at(data: &[u8], offset: usize) -> Option<u64> {
+    Some(u64::from_le_bytes(
+        data.get(offset..offset + 8)?.try_into().ok()?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rc4_keystream() {
+        assert_eq!(
+            rc4(&[0; 8]),
+            [0x08, 0xad, 0x00, 0x98, 0x93, 0xfd, 0xde, 0x68]
+        );
+    }
+
+    #[test]
+    fn reads_entry() {
+        let mut threat = vec![0; 0x34];
+        threat[0x20..0x28].copy_from_slice(&133_000_000_000_000_000u64.to_le_bytes());
+        threat.extend_from_slice(b"HackTool:Win32/Cheat.A\0");
+
+        let path: Vec<u8> = "\\\\?\\C:\\x.exe\0"
+            .encode_utf16()
+            .flat_map(u16::to_le_bytes)
+            .collect();
+        let mut resources = 1u32.to_le_bytes().to_vec();
+        resources.extend_from_slice(&8u32.to_le_bytes());
+        resources.extend_from_slice(&path);
+        resources.extend_from_slice(&[0xaa; SHA1_SIZE]);
+
+        let mut header = vec![0; ENTRY_HEADER_SIZE];
+        header[0x28..0x2c].copy_from_slice(&(threat.len() as u32).to_le_bytes());
+        header[0x2c..0x30].copy_from_slice(&(resources.len() as u32).to_le_bytes());
+
+        let data = [rc4(&header), rc4(&threat), rc4(&resources)].concat();
+        let entries = read_entry(&data).unwrap();
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].path, "C:\\x.exe");
+        assert_eq!(entries[0].threat, "HackTool:Win32/Cheat.A");
+        assert_eq!(entries[0].timestamp, 133_000_000_000_000_000);
+        assert!(entries[0].fields.ends_with(&[0xaa; SHA1_SIZE]));
+    }
+
+    #[test]
+    fn reads_resource_payload() {
+        let payload = b"MZ payload";
+
+        let mut data = vec![0; 0x2c];
+        data[0x8..0xc].copy_from_slice(&4u32.to_le_bytes());
+        data[0x20..0x28].copy_from_slice(&(payload.len() as u64).to_le_bytes());
+        data.extend_from_slice(payload);
+
+        assert_eq!(read_resource(&rc4(&data)).unwrap(), payload);
+    }
+}
@@ -0,0 +1,141 @@
+use serde::{Deserialize, Serialize};
+use windows::Win32::System::Registry::HKEY_LOCAL_MACHINE;
+use windows_registry::LOCAL_MACHINE;
+
+use crate::utils::get_key_last_write_time;
+
+const DEFENDER_KEY: &str = "SOFTWARE\\Microsoft\\Windows Defender";
+const POLICY_KEY: &str = "SOFTWARE\\Policies\\Microsoft\\Windows Defender";
+
+const EXCLUSION_KINDS: [&str; 4] = ["Paths", "Processes", "Extensions", "IpAddresses"];
+
+// подкаталог, имя значения и признак того, что значение 1 означает включенную защиту
+const SETTINGS: [(&str, &str, bool); 8] = [
+    ("", "DisableAntiSpyware", false),
+    ("", "DisableAntiVirus", false),
+    ("Real-Time Protection", "DisableRealtimeMonitoring", false),
+    ("Real-Time Protection", "DisableBehaviorMonitoring", false),
+    ("Real-Time Protection", "DisableOnAccessProtection", false),
+    ("Real-Time Protection", "DisableIOAVProtection", false),
+    ("Spynet", "SpynetReporting", true),
+    ("Features", "TamperProtection", true),
+];
+
+// папки, куда пользователь может писать без прав администратора
+const USER_WRITABLE_DIRS: [&str; 12] = [
+    "\\users\\",
+    "\\appdata\\",
+    "\\temp\\",
+    "\\tmp\\",
+    "\\downloads\\",
+    "\\desktop\\",
+    "\\documents\\",
+    "\\programdata\\",
+    "%userprofile%",
+    "%appdata%",
+    "%localappdata%",
+    "%temp%",
+];
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DefenderExclusion {
+    pub kind: &'static str,
+    pub value: String,
+    pub policy: bool,
+    pub last_write: i64,
+    pub user_writable: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DefenderSetting {
+    pub name: &'static str,
+    pub value: u32,
+    pub protection_enabled: bool,
+    pub policy: bool,
+    pub last_write: i64,
+}
+
+pub fn collect_exclusions() -> Vec<DefenderExclusion> {
+    let mut response = vec![];
+
+    for (root, policy) in [(DEFENDER_KEY, false), (POLICY_KEY, true)] {
+        for kind in EXCLUSION_KINDS {
+            let path = format!("{}\\Exclusions\\{}", root, kind);
+
+            match LOCAL_MACHINE.open(&path) {
+                Ok(key) => {
+                    let Ok(values) = key.values() else {
+                        continue;
+                    };
+
+                    let last_write = get_key_last_write_time(HKEY_LOCAL_MACHINE, &path);
+
+                    for value in values {
+                        response.push(DefenderExclusion {
+                            kind,
+                            user_writable: kind.ne("Extensions") && is_user_writable(&value.0),
+                            value: value.0,
+                            policy,
+                            last_write,
+                        });
+                    }
+                }
+
+                Err(e) => {
+                    if cfg!(dev) {
+                        println!("{e:?}");
+                    }
+                }
+            }
+        }
+    }
+
+    response
+}
+
+pub fn collect_settings() -> Vec<DefenderSetting> {
+    let mut response = vec![];
+
+    for (root, policy) in [(DEFENDER_KEY, false), (POLICY_KEY, true)] {
+        for (subkey, name, enables) in SETTINGS {
+            let path = match subkey.is_empty() {
+                true => root.to_string(),
+                false => format!("{}\\{}", root, subkey),
+            };
+
+            let Ok(key) = LOCAL_MACHINE.open(&path) else {
+                continue;
+            };
+
+            let Ok(value) = key.get_u32(name) else {
+                continue;
+            };
+
+            response.push(DefenderSetting {
+                name,
+                value,
+                protection_enabled: match name {
+                    // 5 - включена, 4 - выключена, младший бит отвечает за состояние
+                    "TamperProtection" => value & 1 == 1,
+                    _ => (value != 0) == enables,
+                },
+                policy,
+                last_write: get_key_last_write_time(HKEY_LOCAL_MACHINE, &path),
+            });
+        }
+    }
+
+    response
+}
+
+pub fn is_user_writable(path: &str) -> bool {
+    let path = path.to_lowercase().replace('/', "\\");
+
+    // исключение целого диска покрывает и пользовательские папки
+    if path.len() <= 3 && path.get(1..2) == Some(":") {
+        return true;
+    }
+
+    let path = format!("{}\\", path.trim_end_matches('\\'));
+    USER_WRITABLE_DIRS.iter().any(|dir| path.contains(dir))
+}
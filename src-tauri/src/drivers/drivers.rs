@@ -0,0 +1,118 @@
+use std::{collections::BTreeMap, path::Path};
+
+use serde::{Deserialize, Serialize};
+use windows::Win32::System::Registry::HKEY_LOCAL_MACHINE;
+use windows_registry::LOCAL_MACHINE;
+
+use crate::{
+    autostart::autostart::start_type_name,
+    utils::{get_key_last_write_time, get_windows_dir, resolve_executable_path},
+};
+
+const SERVICE_KERNEL_DRIVER: u32 = 0x1;
+const SERVICE_FILE_SYSTEM_DRIVER: u32 = 0x2;
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DriverService {
+    pub name: String,
+    pub control_sets: Vec<String>,
+    pub image_path: String,
+    pub path: String,
+    pub start: &'static str,
+    pub group: String,
+    pub last_write: i64,
+    pub exists: bool,
+    pub outside_drivers_dir: bool,
+    // служба удалена из текущего набора, но осталась в резервном
+    pub in_current: bool,
+}
+
+pub fn collect_drivers() -> Vec<DriverService> {
+    let current = current_control_set();
+    let drivers_dir = format!("{}\\System32\\drivers\\", get_windows_dir()).to_lowercase();
+
+    // одна и та же служба обычно есть во всех наборах, объединяем по имени и пути
+    let mut drivers: BTreeMap<(String, String), DriverService> = BTreeMap::new();
+
+    for control_set in control_sets() {
+        let services_path = format!("SYSTEM\\{}\\Services", control_set);
+
+        let Ok(services) = LOCAL_MACHINE.open(&services_path) else {
+            continue;
+        };
+
+        let Ok(names) = services.keys() else {
+            continue;
+        };
+
+        for name in names {
+            let Ok(service) = services.open(&name) else {
+                continue;
+            };
+
+            let Ok(service_type) = service.get_u32("Type") else {
+                continue;
+            };
+
+            if service_type != SERVICE_KERNEL_DRIVER && service_type != SERVICE_FILE_SYSTEM_DRIVER {
+                continue;
+            }
+
+            // без ImagePath система ищет драйвер в System32\drivers по имени службы
+            let image_path = service
+                .get_string("ImagePath")
+                .unwrap_or_else(|_| format!("System32\\drivers\\{}.sys", name));
+
+            let entry = drivers
+                .entry((name.to_lowercase(), image_path.to_lowercase()))
+                .or_insert_with(|| {
+                    let path = resolve_executable_path(&image_path);
+
+                    DriverService {
+                        name: name.clone(),
+                        control_sets: vec![],
+                        exists: Path::new(&path).is_file(),
+                        outside_drivers_dir: !path.to_lowercase().starts_with(&drivers_dir),
+                        image_path: image_path.clone(),
+                        path,
+                        start: start_type_name(service.get_u32("Start").unwrap_or(u32::MAX)),
+                        group: service.get_string("Group").unwrap_or_default(),
+                        last_write: 0,
+                        in_current: false,
+                    }
+                });
+
+            entry.last_write = entry.last_write.max(get_key_last_write_time(
+                HKEY_LOCAL_MACHINE,
+                &format!("{}\\{}", services_path, name),
+            ));
+            entry.in_current |= control_set.eq(&current);
+            entry.control_sets.push(control_set.clone());
+        }
+    }
+
+    drivers.into_values().collect()
+}
+
+fn control_sets() -> Vec<String> {
+    let Ok(system) = LOCAL_MACHINE.open("SYSTEM") else {
+        return vec![];
+    };
+
+    let Ok(names) = system.keys() else {
+        return vec![];
+    };
+
+    names
+        .filter(|name| name.starts_with("ControlSet"))
+        .collect()
+}
+
+pub fn current_control_set() -> String {
+    let current = LOCAL_MACHINE
+        .open("SYSTEM\\Select")
+        .and_then(|key| key.get_u32("Current"))
+        .unwrap_or(1);
+
+    format!("ControlSet{:03}", current)
+}
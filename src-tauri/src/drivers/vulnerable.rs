@@ -0,0 +1,234 @@
+use std::fs;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::utils::reqwest_raw_to_t;
+
+// список можно обновить, загрузив json того же формата или выгрузку loldrivers.io по ссылке,
+// записи без хэшей отбрасываются, иначе они дают совпадения только по имени
+const BUNDLED_VULNERABLE_DRIVERS: &str = include_str!("vulnerable_drivers.json");
+
+const PE_SIGNATURE: u32 = 0x00004550;
+const PE32_MAGIC: u16 = 0x10b;
+const PE32_PLUS_MAGIC: u16 = 0x20b;
+const SECURITY_DIRECTORY_INDEX: usize = 4;
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct VulnerableDriver {
+    pub name: String,
+    pub sha256: String,
+    pub authentihash: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DriverMatch {
+    pub path: String,
+    pub sha256: String,
+    pub authentihash: String,
+    pub driver: VulnerableDriver,
+    // совпало только имя файла, хэши в списке другие или не указаны
+    pub name_only: bool,
+}
+
+pub fn load_vulnerable_drivers() -> Vec<VulnerableDriver> {
+    match serde_json::from_str::<Vec<VulnerableDriver>>(BUNDLED_VULNERABLE_DRIVERS) {
+        Ok(drivers) => drivers.into_iter().filter(has_hash).collect(),
+        Err(e) => {
+            if cfg!(dev) {
+                println!("{e:?}");
+            }
+
+            vec![]
+        }
+    }
+}
+
+pub async fn load_vulnerable_drivers_from_url(url: String) -> Option<Vec<VulnerableDriver>> {
+    let value = reqwest_raw_to_t::<Value>(url).await?;
+
+    match serde_json::from_value::<Vec<VulnerableDriver>>(value.clone()) {
+        Ok(drivers) => Some(drivers.into_iter().filter(has_hash).collect()),
+        Err(_) => {
+            let drivers = parse_loldrivers(&value);
+            (!drivers.is_empty()).then_some(drivers)
+        }
+    }
+}
+
+// [{"Tags": ["RTCore64.sys"], "KnownVulnerableSamples": [{"Filename", "SHA256", "Authentihash": {"SHA256"}}]}]
+pub fn parse_loldrivers(value: &Value) -> Vec<VulnerableDriver> {
+    let text = |value: Option<&Value>| {
+        value
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string()
+    };
+
+    value
+        .as_array()
+        .into_iter()
+        .flatten()
+        .flat_map(|driver| {
+            let tag = text(driver.get("Tags").and_then(|tags| tags.get(0)));
+
+            driver
+                .get("KnownVulnerableSamples")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .map(move |sample| {
+                    let filename = text(sample.get("Filename"));
+
+                    VulnerableDriver {
+                        name: if filename.is_empty() {
+                            tag.clone()
+                        } else {
+                            filename
+                        },
+                        sha256: text(sample.get("SHA256")),
+                        authentihash: text(
+                            sample
+                                .get("Authentihash")
+                                .and_then(|hash| hash.get("SHA256")),
+                        ),
+                    }
+                })
+        })
+        .filter(has_hash)
+        .collect()
+}
+
+fn has_hash(driver: &VulnerableDriver) -> bool {
+    !driver.sha256.is_empty() || !driver.authentihash.is_empty()
+}
+
+pub fn match_vulnerable_driver(path: &str, drivers: &[VulnerableDriver]) -> Option<DriverMatch> {
+    let name = path.rsplit('\\').next().unwrap_or(path).to_lowercase();
+
+    let data = match fs::read(path) {
+        Ok(data) => data,
+        Err(e) => {
+            if cfg!(dev) {
+                println!("{e:?}");
+            }
+
+            return None;
+        }
+    };
+
+    let sha256 = hex::encode(Sha256::digest(&data));
+    let authentihash = authenticode_hash(&data).unwrap_or_default();
+
+    let by_hash = drivers.iter().find(|driver| {
+        (!driver.sha256.is_empty() && driver.sha256.eq_ignore_ascii_case(&sha256))
+            || (!driver.authentihash.is_empty()
+                && driver.authentihash.eq_ignore_ascii_case(&authentihash))
+    });
+
+    let (driver, name_only) = match by_hash {
+        Some(driver) => (driver, false),
+        None => (
+            drivers
+                .iter()
+                .find(|driver| driver.name.to_lowercase().eq(&name))?,
+            true,
+        ),
+    };
+
+    Some(DriverMatch {
+        path: path.to_string(),
+        sha256,
+        authentihash,
+        driver: driver.clone(),
+        name_only,
+    })
+}
+
+// хэш authenticode считается по всему файлу, кроме контрольной суммы,
+// записи о таблице сертификатов и самих сертификатов, поэтому не меняется при переподписи
+// https://learn.microsoft.com/en-us/windows/win32/debug/pe-format#process-for-generating-the-authenticode-pe-image-hash
+pub fn authenticode_hash(data: &[u8]) -> Option<String> {
+    let pe_offset = u32_at(data, 0x3c)? as usize;
+
+    if u32_at(data, pe_offset)? != PE_SIGNATURE {
+        return None;
+    }
+
+    let optional_header = pe_offset + 24;
+    let checksum = optional_header + 64;
+
+    let directories = match u16_at(data, optional_header)? {
+        PE32_MAGIC => optional_header + 96,
+        PE32_PLUS_MAGIC => optional_header + 112,
+        _ => return None,
+    };
+
+    let security_directory = directories + SECURITY_DIRECTORY_INDEX * 8;
+    let certificates_offset = u32_at(data, security_directory)? as usize;
+    let certificates_size = u32_at(data, security_directory + 4)? as usize;
+
+    let certificates_offset = match certificates_offset > 0 && certificates_size > 0 {
+        true => certificates_offset.min(data.len()),
+        false => data.len(),
+    };
+
+    let end = certificates_offset
+        .checked_add(certificates_size)
+        .unwrap_or(data.len())
+        .min(data.len());
+
+    let mut hasher = Sha256::new();
+    hasher.update(data.get(..checksum)?);
+    hasher.update(data.get(checksum + 4..security_directory)?);
+    hasher.update(data.get(security_directory + 8..certificates_offset)?);
+    hasher.update(data.get(end..)?);
+
+    Some(hex::encode(hasher.finalize()))
+}
+
+fn u16_at(data: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(
+        data.get(offset..offset + 2)?.try_into().ok()?,
+    ))
+}
+
+fn u32_at(data: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(
+        data.get(offset..offset + 4)?.try_into().ok()?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_drivers_have_hashes() {
+        let drivers = load_vulnerable_drivers();
+
+        assert!(!drivers.is_empty());
+        assert!(drivers.iter().all(has_hash));
+    }
+
+    #[test]
+    fn parses_loldrivers_samples() {
+        let value = serde_json::json!([{
+            "Tags": ["RTCore64.sys"],
+            "KnownVulnerableSamples": [
+                {"SHA256": "AA", "Authentihash": {"SHA256": "BB"}},
+                {"Filename": "RTCore32.sys", "SHA256": "CC"},
+                {"Filename": "empty.sys"}
+            ]
+        }]);
+
+        let drivers = parse_loldrivers(&value);
+
+        assert_eq!(drivers.len(), 2);
+        assert_eq!(drivers[0].name, "RTCore64.sys");
+        assert_eq!(drivers[0].authentihash, "BB");
+        assert_eq!(drivers[1].name, "RTCore32.sys");
+        assert_eq!(drivers[1].sha256, "CC");
+    }
+}
@@ -0,0 +1,148 @@
+use super::{ese::EseDatabase, record::read_record};
+
+pub const CATALOG_FDP: u32 = 4;
+
+pub const CATALOG_TYPE_TABLE: i16 = 1;
+pub const CATALOG_TYPE_COLUMN: i16 = 2;
+pub const CATALOG_TYPE_LONG_VALUE: i16 = 4;
+
+pub const COLUMN_TYPE_BIT: u32 = 1;
+pub const COLUMN_TYPE_UNSIGNED_BYTE: u32 = 2;
+pub const COLUMN_TYPE_SHORT: u32 = 3;
+pub const COLUMN_TYPE_LONG: u32 = 4;
+pub const COLUMN_TYPE_CURRENCY: u32 = 5;
+pub const COLUMN_TYPE_IEEE_SINGLE: u32 = 6;
+pub const COLUMN_TYPE_IEEE_DOUBLE: u32 = 7;
+pub const COLUMN_TYPE_DATE_TIME: u32 = 8;
+pub const COLUMN_TYPE_BINARY: u32 = 9;
+pub const COLUMN_TYPE_TEXT: u32 = 10;
+pub const COLUMN_TYPE_LONG_BINARY: u32 = 11;
+pub const COLUMN_TYPE_LONG_TEXT: u32 = 12;
+pub const COLUMN_TYPE_UNSIGNED_LONG: u32 = 14;
+pub const COLUMN_TYPE_LONG_LONG: u32 = 15;
+pub const COLUMN_TYPE_GUID: u32 = 16;
+pub const COLUMN_TYPE_UNSIGNED_SHORT: u32 = 17;
+
+pub const CODEPAGE_UNICODE: u32 = 1200;
+
+#[derive(Debug, Clone)]
+pub struct ColumnDefinition {
+    pub id: u32,
+    pub name: String,
+    pub column_type: u32,
+    pub size: u32,
+    pub codepage: u32,
+}
+
+impl ColumnDefinition {
+    pub fn new(id: u32, name: &str, column_type: u32, size: u32) -> Self {
+        Self {
+            id,
+            name: name.to_string(),
+            column_type,
+            size,
+            codepage: 0,
+        }
+    }
+
+    pub fn fixed_size(&self) -> usize {
+        match self.column_type {
+            COLUMN_TYPE_BIT | COLUMN_TYPE_UNSIGNED_BYTE => 1,
+            COLUMN_TYPE_SHORT | COLUMN_TYPE_UNSIGNED_SHORT => 2,
+            COLUMN_TYPE_LONG | COLUMN_TYPE_UNSIGNED_LONG | COLUMN_TYPE_IEEE_SINGLE => 4,
+            COLUMN_TYPE_CURRENCY
+            | COLUMN_TYPE_IEEE_DOUBLE
+            | COLUMN_TYPE_DATE_TIME
+            | COLUMN_TYPE_LONG_LONG => 8,
+            COLUMN_TYPE_GUID => 16,
+            _ => self.size as usize,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TableDefinition {
+    pub name: String,
+    pub object_id: u32,
+    pub fdp: u32,
+    pub long_value_fdp: Option<u32>,
+    pub columns: Vec<ColumnDefinition>,
+}
+
+impl TableDefinition {
+    pub fn column(&self, name: &str) -> Option<&ColumnDefinition> {
+        self.columns
+            .iter()
+            .find(|c| c.name.eq_ignore_ascii_case(name))
+    }
+}
+
+// MSysObjects описывает сам себя, но чтобы прочитать его нужно заранее знать его структуру
+fn catalog_definition() -> TableDefinition {
+    TableDefinition {
+        name: String::from("MSysObjects"),
+        object_id: 2,
+        fdp: CATALOG_FDP,
+        long_value_fdp: None,
+        columns: vec![
+            ColumnDefinition::new(1, "ObjidTable", COLUMN_TYPE_LONG, 4),
+            ColumnDefinition::new(2, "Type", COLUMN_TYPE_SHORT, 2),
+            ColumnDefinition::new(3, "Id", COLUMN_TYPE_LONG, 4),
+            ColumnDefinition::new(4, "ColtypOrPgnoFDP", COLUMN_TYPE_LONG, 4),
+            ColumnDefinition::new(5, "SpaceUsage", COLUMN_TYPE_LONG, 4),
+            ColumnDefinition::new(6, "Flags", COLUMN_TYPE_LONG, 4),
+            ColumnDefinition::new(7, "PagesOrLocale", COLUMN_TYPE_LONG, 4),
+            ColumnDefinition::new(8, "RootFlag", COLUMN_TYPE_BIT, 1),
+            ColumnDefinition::new(9, "RecordOffset", COLUMN_TYPE_SHORT, 2),
+            ColumnDefinition::new(10, "LCMapFlags", COLUMN_TYPE_LONG, 4),
+            ColumnDefinition::new(11, "KeyMost", COLUMN_TYPE_UNSIGNED_SHORT, 2),
+            ColumnDefinition::new(128, "Name", COLUMN_TYPE_TEXT, 255),
+        ],
+    }
+}
+
+pub fn read_catalog(db: &EseDatabase) -> Vec<TableDefinition> {
+    let definition = catalog_definition();
+    let mut tables: Vec<TableDefinition> = vec![];
+
+    db.walk_tree(CATALOG_FDP, &mut |entry| {
+        let record = read_record(db, &definition, entry.data, None);
+
+        let object_id = record.get_i64("ObjidTable").unwrap_or(0) as u32;
+        let kind = record.get_i64("Type").unwrap_or(0) as i16;
+        let name = record.get_string("Name").unwrap_or_default();
+
+        match kind {
+            CATALOG_TYPE_TABLE => tables.push(TableDefinition {
+                name,
+                object_id,
+                fdp: record.get_i64("ColtypOrPgnoFDP").unwrap_or(0) as u32,
+                long_value_fdp: None,
+                columns: vec![],
+            }),
+            CATALOG_TYPE_COLUMN => {
+                if let Some(table) = tables.iter_mut().find(|t| t.object_id == object_id) {
+                    table.columns.push(ColumnDefinition {
+                        id: record.get_i64("Id").unwrap_or(0) as u32,
+                        name,
+                        column_type: record.get_i64("ColtypOrPgnoFDP").unwrap_or(0) as u32,
+                        size: record.get_i64("SpaceUsage").unwrap_or(0) as u32,
+                        codepage: record.get_i64("PagesOrLocale").unwrap_or(0) as u32,
+                    });
+                }
+            }
+            CATALOG_TYPE_LONG_VALUE => {
+                if let Some(table) = tables.iter_mut().find(|t| t.object_id == object_id) {
+                    table.long_value_fdp = record.get_i64("ColtypOrPgnoFDP").map(|fdp| fdp as u32);
+                }
+            }
+            _ => {}
+        }
+    });
+
+    for table in tables.iter_mut() {
+        table.columns.sort_by_key(|c| c.id);
+    }
+
+    tables
+}
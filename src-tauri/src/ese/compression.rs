@@ -0,0 +1,191 @@
+use super::page::{read_u16, read_u32};
+
+const COMPRESSION_7BIT_ASCII: u8 = 1;
+const COMPRESSION_7BIT_UNICODE: u8 = 2;
+const COMPRESSION_XPRESS: u8 = 3;
+
+// https://github.com/libyal/libesedb/blob/main/documentation/Extensible%20Storage%20Engine%20(ESE)%20Database%20File%20(EDB)%20format.asciidoc#compression
+pub fn decompress(data: &[u8]) -> Option<Vec<u8>> {
+    let header = *data.first()?;
+
+    match header >> 3 {
+        COMPRESSION_7BIT_ASCII => Some(decompress_7bit(data)),
+        COMPRESSION_7BIT_UNICODE => Some(
+            decompress_7bit(data)
+                .into_iter()
+                .flat_map(|b| [b, 0])
+                .collect(),
+        ),
+        COMPRESSION_XPRESS => {
+            let size = read_u16(data, 1)? as usize;
+            decompress_xpress(data.get(3..)?, size)
+        }
+        _ => None,
+    }
+}
+
+fn decompress_7bit(data: &[u8]) -> Vec<u8> {
+    if data.len() < 2 {
+        return vec![];
+    }
+
+    // младшие 3 бита заголовка - количество использованных бит в последнем байте минус один
+    let total_bits = (data.len() - 2) * 8 + (data[0] & 0x07) as usize + 1;
+    let count = total_bits / 7;
+
+    let mut out = Vec::with_capacity(count);
+    let mut value: u32 = 0;
+    let mut bits = 0;
+
+    for byte in &data[1..] {
+        value |= (*byte as u32) << bits;
+        bits += 8;
+
+        while bits >= 7 && out.len() < count {
+            out.push((value & 0x7f) as u8);
+            value >>= 7;
+            bits -= 7;
+        }
+    }
+
+    out
+}
+
+// [MS-XCA] 2.4 plain lz77 decompression
+// длина ссылки может быть до 4 ГиБ, поэтому вывод ограничен размером из заголовка
+pub fn decompress_xpress(data: &[u8], size: usize) -> Option<Vec<u8>> {
+    let mut out: Vec<u8> = Vec::with_capacity(size);
+    let mut position = 0;
+    let mut flags: u32 = 0;
+    let mut flag_count = 0;
+    let mut half_byte_position: Option<usize> = None;
+
+    while out.len() < size {
+        if flag_count == 0 {
+            if position + 4 > data.len() {
+                break;
+            }
+
+            flags = read_u32(data, position)?;
+            position += 4;
+            flag_count = 32;
+        }
+
+        flag_count -= 1;
+
+        if flags & (1 << flag_count) == 0 {
+            match data.get(position) {
+                Some(byte) => out.push(*byte),
+                None => break,
+            }
+
+            position += 1;
+            continue;
+        }
+
+        if position + 2 > data.len() {
+            break;
+        }
+
+        let match_data = read_u16(data, position)? as usize;
+        position += 2;
+
+        let mut length = match_data % 8;
+        let offset = match_data / 8 + 1;
+
+        if length == 7 {
+            match half_byte_position {
+                None => {
+                    length = (*data.get(position)? % 16) as usize;
+                    half_byte_position = Some(position);
+                    position += 1;
+                }
+                Some(half) => {
+                    length = (data[half] / 16) as usize;
+                    half_byte_position = None;
+                }
+            }
+
+            if length == 15 {
+                length = *data.get(position)? as usize;
+                position += 1;
+
+                if length == 255 {
+                    length = read_u16(data, position)? as usize;
+                    position += 2;
+
+                    if length == 0 {
+                        length = read_u32(data, position)? as usize;
+                        position += 4;
+                    }
+
+                    length = length.checked_sub(15 + 7)?;
+                }
+
+                length += 15;
+            }
+
+            length += 7;
+        }
+
+        length = (length + 3).min(size - out.len());
+
+        let start = out.len().checked_sub(offset)?;
+        for i in 0..length {
+            out.push(out[start + i]);
+        }
+    }
+
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn xpress_literals_and_match() {
+        // три литерала, затем ссылка на смещение 3 длиной 6
+        let mut data = (1u32 << 28).to_le_bytes().to_vec();
+        data.extend_from_slice(b"abc");
+        data.extend_from_slice(&((2u16 << 3) | 3).to_le_bytes());
+
+        assert_eq!(decompress_xpress(&data, 9).unwrap(), b"abcabcabc");
+    }
+
+    #[test]
+    fn xpress_rejects_offset_before_start() {
+        let mut data = (1u32 << 31).to_le_bytes().to_vec();
+        data.extend_from_slice(&0u16.to_le_bytes());
+
+        assert!(decompress_xpress(&data, 16).is_none());
+    }
+
+    #[test]
+    fn xpress_stops_at_declared_size() {
+        // литерал и ссылка с 32-битной длиной почти в 4 ГиБ
+        let mut data = (1u32 << 30).to_le_bytes().to_vec();
+        data.push(b'a');
+        data.extend_from_slice(&7u16.to_le_bytes());
+        data.extend_from_slice(&[15, 255]);
+        data.extend_from_slice(&0u16.to_le_bytes());
+        data.extend_from_slice(&u32::MAX.to_le_bytes());
+
+        assert_eq!(decompress_xpress(&data, 5).unwrap(), b"aaaaa");
+
+        let mut compressed = vec![COMPRESSION_XPRESS << 3];
+        compressed.extend_from_slice(&5u16.to_le_bytes());
+        compressed.extend_from_slice(&data);
+
+        assert_eq!(decompress(&compressed).unwrap(), b"aaaaa");
+    }
+
+    #[test]
+    fn seven_bit_ascii() {
+        let packed = (0x61u32 | (0x62 << 7) | (0x63 << 14)).to_le_bytes();
+        let mut data = vec![(COMPRESSION_7BIT_ASCII << 3) | 4];
+        data.extend_from_slice(&packed[..3]);
+
+        assert_eq!(decompress(&data).unwrap(), b"abc");
+    }
+}
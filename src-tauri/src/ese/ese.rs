@@ -0,0 +1,313 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fs::File,
+    ops::Deref,
+    path::Path,
+};
+
+use memmap2::Mmap;
+
+use super::{
+    catalog::{read_catalog, TableDefinition},
+    page::{read_u32, Page, PageEntry},
+    record::{read_record, EseRecord},
+};
+
+const FILE_SIGNATURE: u32 = 0x89abcdef;
+const FORMAT_REVISION_EXTENDED_PAGE_HEADER: u32 = 0x11;
+const MAX_TREE_DEPTH: usize = 64;
+
+// файл отображаем в память, разбор из буфера нужен для уже прочитанных данных
+enum EseData {
+    Mapped(Mmap),
+    Owned(Vec<u8>),
+}
+
+impl Deref for EseData {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            EseData::Mapped(map) => map,
+            EseData::Owned(data) => data,
+        }
+    }
+}
+
+// только чтение страниц напрямую из файла, без esent и без журналов транзакций
+pub struct EseDatabase {
+    data: EseData,
+    pub page_size: usize,
+    pub format_version: u32,
+    pub format_revision: u32,
+    pub tables: Vec<TableDefinition>,
+}
+
+impl EseDatabase {
+    pub fn open(path: &Path) -> Option<Self> {
+        let file = match File::open(path) {
+            Ok(file) => file,
+            Err(e) => {
+                if cfg!(dev) {
+                    println!("{e:?}");
+                }
+
+                return None;
+            }
+        };
+
+        match unsafe { Mmap::map(&file) } {
+            Ok(map) => Self::from_data(EseData::Mapped(map)),
+            Err(e) => {
+                if cfg!(dev) {
+                    println!("{e:?}");
+                }
+
+                None
+            }
+        }
+    }
+
+    pub fn from_bytes(data: Vec<u8>) -> Option<Self> {
+        Self::from_data(EseData::Owned(data))
+    }
+
+    fn from_data(data: EseData) -> Option<Self> {
+        if read_u32(&data, 4)? != FILE_SIGNATURE {
+            return None;
+        }
+
+        let page_size = read_u32(&data, 236)? as usize;
+        if !matches!(page_size, 2048 | 4096 | 8192 | 16384 | 32768) {
+            return None;
+        }
+
+        let mut db = Self {
+            format_version: read_u32(&data, 8)?,
+            format_revision: read_u32(&data, 232)?,
+            page_size,
+            data,
+            tables: vec![],
+        };
+
+        db.tables = read_catalog(&db);
+
+        Some(db)
+    }
+
+    pub fn is_large_page(&self) -> bool {
+        self.format_revision >= FORMAT_REVISION_EXTENDED_PAGE_HEADER && self.page_size >= 16384
+    }
+
+    pub fn page(&self, number: u32) -> Option<Page<'_>> {
+        // нулевая и первая страницы файла заняты заголовком и его копией
+        let offset = (number as usize + 1).checked_mul(self.page_size)?;
+        let data = self.data.get(offset..offset + self.page_size)?;
+        Page::parse(number, data, self.is_large_page())
+    }
+
+    pub fn table(&self, name: &str) -> Option<&TableDefinition> {
+        self.tables.iter().find(|t| t.name.eq(name))
+    }
+
+    pub fn table_names(&self) -> Vec<String> {
+        self.tables.iter().map(|t| t.name.clone()).collect()
+    }
+
+    pub fn walk_tree(&self, root: u32, visit: &mut dyn FnMut(&PageEntry)) {
+        let mut visited = HashSet::new();
+        self.walk_page(root, 0, &mut visited, visit);
+    }
+
+    fn walk_page(
+        &self,
+        number: u32,
+        depth: usize,
+        visited: &mut HashSet<u32>,
+        visit: &mut dyn FnMut(&PageEntry),
+    ) {
+        if depth > MAX_TREE_DEPTH || !visited.insert(number) {
+            return;
+        }
+
+        let Some(page) = self.page(number) else {
+            return;
+        };
+
+        if page.is_empty() || page.is_space_tree() {
+            return;
+        }
+
+        for entry in page.entries() {
+            if page.is_parent() {
+                if let Some(child) = read_u32(entry.data, 0) {
+                    self.walk_page(child, depth + 1, visited, visit);
+                }
+            } else if page.is_leaf() {
+                visit(&entry);
+            }
+        }
+    }
+
+    // ключ long value: идентификатор (big endian) для заголовка и идентификатор + смещение для частей
+    pub fn read_long_values(&self, table: &TableDefinition) -> HashMap<u32, Vec<u8>> {
+        let mut segments: HashMap<u32, BTreeMap<u32, Vec<u8>>> = HashMap::new();
+
+        if let Some(fdp) = table.long_value_fdp {
+            self.walk_tree(fdp, &mut |entry| {
+                if entry.key.len() != 8 {
+                    return;
+                }
+
+                let id =
+                    u32::from_be_bytes([entry.key[0], entry.key[1], entry.key[2], entry.key[3]]);
+                let offset =
+                    u32::from_be_bytes([entry.key[4], entry.key[5], entry.key[6], entry.key[7]]);
+
+                segments
+                    .entry(id)
+                    .or_default()
+                    .insert(offset, entry.data.to_vec());
+            });
+        }
+
+        segments
+            .into_iter()
+            .map(|(id, parts)| (id, parts.into_values().flatten().collect()))
+            .collect()
+    }
+
+    pub fn read_table(&self, name: &str) -> Vec<EseRecord> {
+        let Some(table) = self.table(name) else {
+            return vec![];
+        };
+
+        let long_values = self.read_long_values(table);
+        let mut records = vec![];
+
+        self.walk_tree(table.fdp, &mut |entry| {
+            records.push(read_record(self, table, entry.data, Some(&long_values)));
+        });
+
+        records
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ese::{
+        catalog::{CODEPAGE_UNICODE, COLUMN_TYPE_LONG, COLUMN_TYPE_TEXT},
+        page::{PAGE_FLAG_LEAF, PAGE_FLAG_ROOT},
+    };
+
+    const PAGE_SIZE: usize = 4096;
+
+    // фиксированные колонки, битовая карта null без пустых значений и переменные колонки
+    fn record(fixed: &[&[u8]], variable: &[&[u8]]) -> Vec<u8> {
+        let fixed_size: usize = fixed.iter().map(|f| f.len()).sum();
+        let bitmap_size = fixed.len().div_ceil(8);
+
+        let mut data = vec![fixed.len() as u8, (127 + variable.len()) as u8];
+        data.extend_from_slice(&((4 + fixed_size + bitmap_size) as u16).to_le_bytes());
+        fixed.iter().for_each(|f| data.extend_from_slice(f));
+        data.extend(vec![0; bitmap_size]);
+
+        let mut end = 0;
+        for value in variable {
+            end += value.len();
+            data.extend_from_slice(&(end as u16).to_le_bytes());
+        }
+        variable.iter().for_each(|v| data.extend_from_slice(v));
+
+        data
+    }
+
+    // корневая страница-лист, нулевой тег под общий ключ, записи без ключа
+    fn page(records: &[Vec<u8>]) -> Vec<u8> {
+        let mut data = vec![0; PAGE_SIZE];
+        data[34..36].copy_from_slice(&((records.len() + 1) as u16).to_le_bytes());
+        data[36..40].copy_from_slice(&(PAGE_FLAG_ROOT | PAGE_FLAG_LEAF).to_le_bytes());
+
+        let mut offset = 0;
+        for (index, record) in records.iter().enumerate() {
+            let entry = [&0u16.to_le_bytes()[..], record].concat();
+            data[40 + offset..40 + offset + entry.len()].copy_from_slice(&entry);
+
+            let tag = ((offset as u32) << 16) | entry.len() as u32;
+            let position = PAGE_SIZE - 4 * (index + 2);
+            data[position..position + 4].copy_from_slice(&tag.to_le_bytes());
+
+            offset += entry.len();
+        }
+
+        data
+    }
+
+    fn catalog_record(kind: i16, id: i32, coltyp: i32, locale: i32, name: &str) -> Vec<u8> {
+        record(
+            &[
+                &10i32.to_le_bytes(),
+                &kind.to_le_bytes(),
+                &id.to_le_bytes(),
+                &coltyp.to_le_bytes(),
+                &4i32.to_le_bytes(),
+                &0i32.to_le_bytes(),
+                &locale.to_le_bytes(),
+            ],
+            &[name.as_bytes()],
+        )
+    }
+
+    fn database() -> Vec<u8> {
+        let mut data = vec![0; PAGE_SIZE * 7];
+        data[4..8].copy_from_slice(&FILE_SIGNATURE.to_le_bytes());
+        data[8..12].copy_from_slice(&0x620u32.to_le_bytes());
+        data[232..236].copy_from_slice(&0x0cu32.to_le_bytes());
+        data[236..240].copy_from_slice(&(PAGE_SIZE as u32).to_le_bytes());
+
+        let catalog = page(&[
+            catalog_record(1, 5, 5, 0, "Test"),
+            catalog_record(2, 1, COLUMN_TYPE_LONG as i32, 0, "Id"),
+            catalog_record(
+                2,
+                128,
+                COLUMN_TYPE_TEXT as i32,
+                CODEPAGE_UNICODE as i32,
+                "Name",
+            ),
+        ]);
+
+        let name: Vec<u8> = "hello".encode_utf16().flat_map(u16::to_le_bytes).collect();
+        let table = page(&[record(&[&42i32.to_le_bytes()], &[&name])]);
+
+        data[PAGE_SIZE * 5..PAGE_SIZE * 6].copy_from_slice(&catalog);
+        data[PAGE_SIZE * 6..].copy_from_slice(&table);
+
+        data
+    }
+
+    #[test]
+    fn reads_catalog_and_table() {
+        let db = EseDatabase::from_bytes(database()).unwrap();
+
+        assert_eq!(db.table_names(), vec!["Test"]);
+        assert!(!db.is_large_page());
+
+        let records = db.read_table("Test");
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].get_i64("Id"), Some(42));
+        assert_eq!(records[0].get_string("Name").as_deref(), Some("hello"));
+    }
+
+    #[test]
+    fn rejects_bad_header() {
+        let mut data = database();
+        data[4] = 0;
+        assert!(EseDatabase::from_bytes(data).is_none());
+
+        let mut data = database();
+        data[236..240].copy_from_slice(&1000u32.to_le_bytes());
+        assert!(EseDatabase::from_bytes(data).is_none());
+    }
+}
@@ -0,0 +1,5 @@
+pub mod catalog;
+pub mod compression;
+pub mod ese;
+pub mod page;
+pub mod record;
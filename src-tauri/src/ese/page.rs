@@ -0,0 +1,193 @@
+// https://github.com/libyal/libesedb/blob/main/documentation/Extensible%20Storage%20Engine%20(ESE)%20Database%20File%20(EDB)%20format.asciidoc#database-page
+pub const PAGE_FLAG_ROOT: u32 = 0x0001;
+pub const PAGE_FLAG_LEAF: u32 = 0x0002;
+pub const PAGE_FLAG_PARENT: u32 = 0x0004;
+pub const PAGE_FLAG_EMPTY: u32 = 0x0008;
+pub const PAGE_FLAG_SPACE_TREE: u32 = 0x0020;
+
+pub const TAG_FLAG_DEFUNCT: u8 = 0x02;
+pub const TAG_FLAG_COMMON_KEY: u8 = 0x04;
+
+#[derive(Debug, Clone, Copy)]
+pub struct PageTag {
+    pub offset: usize,
+    pub size: usize,
+    pub flags: u8,
+}
+
+#[derive(Debug, Clone)]
+pub struct PageEntry<'a> {
+    pub flags: u8,
+    pub key: Vec<u8>,
+    pub data: &'a [u8],
+}
+
+pub struct Page<'a> {
+    pub number: u32,
+    pub previous: u32,
+    pub next: u32,
+    pub fdp_object_id: u32,
+    pub flags: u32,
+    data: &'a [u8],
+    header_size: usize,
+    tag_count: usize,
+    large: bool,
+}
+
+impl<'a> Page<'a> {
+    pub fn parse(number: u32, data: &'a [u8], large: bool) -> Option<Self> {
+        if data.len() < 40 {
+            return None;
+        }
+
+        Some(Self {
+            number,
+            previous: read_u32(data, 16)?,
+            next: read_u32(data, 20)?,
+            fdp_object_id: read_u32(data, 24)?,
+            flags: read_u32(data, 36)?,
+            tag_count: read_u16(data, 34)? as usize,
+            header_size: if large { 80 } else { 40 },
+            data,
+            large,
+        })
+    }
+
+    pub fn is_root(&self) -> bool {
+        self.flags & PAGE_FLAG_ROOT != 0
+    }
+
+    pub fn is_leaf(&self) -> bool {
+        self.flags & PAGE_FLAG_LEAF != 0
+    }
+
+    pub fn is_parent(&self) -> bool {
+        self.flags & PAGE_FLAG_PARENT != 0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.flags & PAGE_FLAG_EMPTY != 0
+    }
+
+    pub fn is_space_tree(&self) -> bool {
+        self.flags & PAGE_FLAG_SPACE_TREE != 0
+    }
+
+    pub fn tag(&self, index: usize) -> Option<PageTag> {
+        if index >= self.tag_count {
+            return None;
+        }
+
+        let position = self.data.len().checked_sub(4 * (index + 1))?;
+        let raw = read_u32(self.data, position)?;
+
+        if self.large {
+            let offset = ((raw >> 16) & 0x7fff) as usize;
+            let size = (raw & 0x7fff) as usize;
+            let start = self.header_size + offset;
+
+            // на страницах от 16 КиБ флаги тега перенесены в само значение
+            let flags = if size >= 2 {
+                self.data.get(start + 1).map(|b| b >> 5).unwrap_or(0)
+            } else {
+                0
+            };
+
+            Some(PageTag {
+                offset,
+                size,
+                flags,
+            })
+        } else {
+            Some(PageTag {
+                offset: ((raw >> 16) & 0x1fff) as usize,
+                size: (raw & 0x1fff) as usize,
+                flags: (raw >> 29) as u8,
+            })
+        }
+    }
+
+    pub fn tag_data(&self, tag: &PageTag) -> Option<&'a [u8]> {
+        let start = self.header_size + tag.offset;
+        self.data.get(start..start + tag.size)
+    }
+
+    // префикс ключа страницы, на который ссылаются записи с общим ключом
+    fn common_key(&self) -> &'a [u8] {
+        if self.is_root() {
+            return &[];
+        }
+
+        self.tag(0)
+            .and_then(|tag| self.tag_data(&tag))
+            .unwrap_or(&[])
+    }
+
+    pub fn entries(&self) -> Vec<PageEntry<'a>> {
+        let common_key = self.common_key();
+
+        (1..self.tag_count)
+            .filter_map(|index| {
+                let tag = self.tag(index)?;
+
+                if tag.flags & TAG_FLAG_DEFUNCT != 0 {
+                    return None;
+                }
+
+                let data = self.tag_data(&tag)?;
+                self.read_entry(tag.flags, data, common_key)
+            })
+            .collect()
+    }
+
+    fn read_entry(&self, flags: u8, data: &'a [u8], common_key: &[u8]) -> Option<PageEntry<'a>> {
+        let mut offset = 0;
+        let mut key = vec![];
+
+        if flags & TAG_FLAG_COMMON_KEY != 0 {
+            let common_size = self.read_masked_u16(data, offset)? as usize;
+            key.extend_from_slice(common_key.get(..common_size).unwrap_or(common_key));
+            offset += 2;
+        }
+
+        let local_size = self.read_masked_u16(data, offset)? as usize;
+        offset += 2;
+
+        key.extend_from_slice(data.get(offset..offset + local_size)?);
+        offset += local_size;
+
+        Some(PageEntry {
+            flags,
+            key,
+            data: data.get(offset..)?,
+        })
+    }
+
+    fn read_masked_u16(&self, data: &[u8], offset: usize) -> Option<u16> {
+        let value = read_u16(data, offset)?;
+
+        if self.large && offset == 0 {
+            Some(value & 0x1fff)
+        } else {
+            Some(value)
+        }
+    }
+}
+
+pub fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(
+        data.get(offset..offset + 2)?.try_into().ok()?,
+    ))
+}
+
+pub fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(
+        data.get(offset..offset + 4)?.try_into().ok()?,
+    ))
+}
+
+pub fn read_u64(data: &[u8], offset: usize) -> Option<u64> {
+    Some(u64::from_le_bytes(
+        data.get(offset..offset + 8)?.try_into().ok()?,
+    ))
+}
@@ -0,0 +1,362 @@
+use std::collections::HashMap;
+
+use super::{
+    catalog::{
+        ColumnDefinition, TableDefinition, CODEPAGE_UNICODE, COLUMN_TYPE_BINARY, COLUMN_TYPE_BIT,
+        COLUMN_TYPE_CURRENCY, COLUMN_TYPE_DATE_TIME, COLUMN_TYPE_GUID, COLUMN_TYPE_IEEE_DOUBLE,
+        COLUMN_TYPE_IEEE_SINGLE, COLUMN_TYPE_LONG, COLUMN_TYPE_LONG_BINARY, COLUMN_TYPE_LONG_LONG,
+        COLUMN_TYPE_LONG_TEXT, COLUMN_TYPE_SHORT, COLUMN_TYPE_TEXT, COLUMN_TYPE_UNSIGNED_BYTE,
+        COLUMN_TYPE_UNSIGNED_LONG, COLUMN_TYPE_UNSIGNED_SHORT,
+    },
+    compression::decompress,
+    ese::EseDatabase,
+    page::{read_u16, read_u32},
+};
+use crate::utils::guid_bytes_to_string;
+
+const VALUE_FLAG_COMPRESSED: u8 = 0x02;
+const VALUE_FLAG_LONG_VALUE: u8 = 0x04;
+const VALUE_FLAG_MULTI_VALUE: u8 = 0x08;
+const VALUE_FLAG_MULTI_VALUE_SIZE: u8 = 0x10;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum EseValue {
+    Bit(bool),
+    UnsignedByte(u8),
+    Short(i16),
+    UnsignedShort(u16),
+    Long(i32),
+    UnsignedLong(u32),
+    LongLong(i64),
+    Currency(i64),
+    Single(f32),
+    Double(f64),
+    DateTime(f64),
+    Guid([u8; 16]),
+    Binary(Vec<u8>),
+    Text(String),
+    Multi(Vec<EseValue>),
+}
+
+impl EseValue {
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            EseValue::Bit(v) => Some(*v as i64),
+            EseValue::UnsignedByte(v) => Some(*v as i64),
+            EseValue::Short(v) => Some(*v as i64),
+            EseValue::UnsignedShort(v) => Some(*v as i64),
+            EseValue::Long(v) => Some(*v as i64),
+            EseValue::UnsignedLong(v) => Some(*v as i64),
+            EseValue::LongLong(v) | EseValue::Currency(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            EseValue::Single(v) => Some(*v as f64),
+            EseValue::Double(v) | EseValue::DateTime(v) => Some(*v),
+            _ => self.as_i64().map(|v| v as f64),
+        }
+    }
+
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            EseValue::Binary(v) => Some(v),
+            EseValue::Text(v) => Some(v.as_bytes()),
+            _ => None,
+        }
+    }
+
+    pub fn as_string(&self) -> Option<String> {
+        match self {
+            EseValue::Text(v) => Some(v.clone()),
+            EseValue::Guid(v) => Some(guid_bytes_to_string(v)),
+            EseValue::Binary(v) => Some(hex::encode(v)),
+            EseValue::Multi(values) => Some(
+                values
+                    .iter()
+                    .filter_map(|v| v.as_string())
+                    .collect::<Vec<_>>()
+                    .join("; "),
+            ),
+            _ => self.as_i64().map(|v| v.to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct EseRecord {
+    pub values: HashMap<String, EseValue>,
+}
+
+impl EseRecord {
+    pub fn get(&self, name: &str) -> Option<&EseValue> {
+        self.values.get(name)
+    }
+
+    pub fn get_i64(&self, name: &str) -> Option<i64> {
+        self.get(name)?.as_i64()
+    }
+
+    pub fn get_f64(&self, name: &str) -> Option<f64> {
+        self.get(name)?.as_f64()
+    }
+
+    pub fn get_bytes(&self, name: &str) -> Option<&[u8]> {
+        self.get(name)?.as_bytes()
+    }
+
+    pub fn get_string(&self, name: &str) -> Option<String> {
+        self.get(name)?.as_string()
+    }
+}
+
+// https://github.com/libyal/libesedb/blob/main/documentation/Extensible%20Storage%20Engine%20(ESE)%20Database%20File%20(EDB)%20format.asciidoc#data-definition
+pub fn read_record(
+    db: &EseDatabase,
+    table: &TableDefinition,
+    data: &[u8],
+    long_values: Option<&HashMap<u32, Vec<u8>>>,
+) -> EseRecord {
+    let mut record = EseRecord::default();
+
+    if data.len() < 4 {
+        return record;
+    }
+
+    let last_fixed = data[0] as u32;
+    let last_variable = data[1] as u32;
+    let variable_offset = read_u16(data, 2).unwrap_or(0) as usize;
+
+    // фиксированные колонки идут подряд по возрастанию id, за ними битовая карта null значений
+    let bitmap_size = (last_fixed as usize).div_ceil(8);
+    let bitmap_offset = variable_offset.saturating_sub(bitmap_size);
+    let mut offset = 4;
+
+    for column in table.columns.iter().filter(|c| c.id <= last_fixed) {
+        let size = column.fixed_size();
+        let bit = (column.id - 1) as usize;
+
+        let is_null = data
+            .get(bitmap_offset + bit / 8)
+            .map(|b| b & (1 << (bit % 8)) != 0)
+            .unwrap_or(true);
+
+        if let Some(value) = data.get(offset..offset + size) {
+            if !is_null {
+                if let Some(value) = convert_value(column, value) {
+                    record.values.insert(column.name.clone(), value);
+                }
+            }
+        }
+
+        offset += size;
+    }
+
+    // переменные колонки: массив конечных смещений, старший бит означает пустое значение
+    let variable_count = if last_variable >= 128 {
+        (last_variable - 127) as usize
+    } else {
+        0
+    };
+    let variable_data_offset = variable_offset + variable_count * 2;
+    let mut previous_end = 0;
+
+    for index in 0..variable_count {
+        let Some(raw) = read_u16(data, variable_offset + index * 2) else {
+            break;
+        };
+
+        let end = (raw & 0x7fff) as usize;
+        let id = 128 + index as u32;
+
+        if raw & 0x8000 == 0 && end > previous_end {
+            if let (Some(column), Some(value)) = (
+                table.columns.iter().find(|c| c.id == id),
+                data.get(variable_data_offset + previous_end..variable_data_offset + end),
+            ) {
+                if let Some(value) = convert_value(column, value) {
+                    record.values.insert(column.name.clone(), value);
+                }
+            }
+        }
+
+        previous_end = end;
+    }
+
+    let tagged_offset = variable_data_offset + previous_end;
+    if let Some(tagged) = data.get(tagged_offset..) {
+        read_tagged(db, table, tagged, long_values, &mut record);
+    }
+
+    record
+}
+
+fn read_tagged(
+    db: &EseDatabase,
+    table: &TableDefinition,
+    tagged: &[u8],
+    long_values: Option<&HashMap<u32, Vec<u8>>>,
+    record: &mut EseRecord,
+) {
+    let large = db.is_large_page();
+    let mask = if large { 0x7fff } else { 0x3fff };
+
+    let Some(first_offset) = read_u16(tagged, 2) else {
+        return;
+    };
+
+    let count = (first_offset & mask) as usize / 4;
+
+    for index in 0..count {
+        let (Some(id), Some(raw_offset)) =
+            (read_u16(tagged, index * 4), read_u16(tagged, index * 4 + 2))
+        else {
+            break;
+        };
+
+        let start = (raw_offset & mask) as usize;
+        let end = if index + 1 < count {
+            read_u16(tagged, (index + 1) * 4 + 2)
+                .map(|o| (o & mask) as usize)
+                .unwrap_or(tagged.len())
+        } else {
+            tagged.len()
+        };
+
+        let Some(mut value) = tagged.get(start..end) else {
+            continue;
+        };
+
+        let mut flags = 0;
+        if (large || raw_offset & 0x4000 != 0) && !value.is_empty() {
+            flags = value[0];
+            value = &value[1..];
+        }
+
+        let Some(column) = table.columns.iter().find(|c| c.id == id as u32) else {
+            continue;
+        };
+
+        if let Some(value) = read_tagged_value(column, flags, value, long_values) {
+            record.values.insert(column.name.clone(), value);
+        }
+    }
+}
+
+fn read_tagged_value(
+    column: &ColumnDefinition,
+    flags: u8,
+    value: &[u8],
+    long_values: Option<&HashMap<u32, Vec<u8>>>,
+) -> Option<EseValue> {
+    if flags & VALUE_FLAG_MULTI_VALUE != 0 {
+        return Some(EseValue::Multi(
+            split_multi_value(value)
+                .into_iter()
+                .filter_map(|v| convert_value(column, &v))
+                .collect(),
+        ));
+    }
+
+    if flags & VALUE_FLAG_MULTI_VALUE_SIZE != 0 {
+        let size = *value.first()? as usize;
+        let values = [value.get(1..1 + size)?, value.get(1 + size..)?];
+        return Some(EseValue::Multi(
+            values
+                .iter()
+                .filter_map(|v| convert_value(column, v))
+                .collect(),
+        ));
+    }
+
+    let mut owned = if flags & VALUE_FLAG_LONG_VALUE != 0 {
+        let id = read_u32(value, 0)?;
+        long_values?.get(&id)?.clone()
+    } else {
+        value.to_vec()
+    };
+
+    if flags & VALUE_FLAG_COMPRESSED != 0 {
+        if let Some(decompressed) = decompress(&owned) {
+            owned = decompressed;
+        }
+    }
+
+    convert_value(column, &owned)
+}
+
+fn split_multi_value(value: &[u8]) -> Vec<Vec<u8>> {
+    let Some(first) = read_u16(value, 0) else {
+        return vec![];
+    };
+
+    let count = (first & 0x7fff) as usize / 2;
+    let offsets: Vec<usize> = (0..count)
+        .filter_map(|i| read_u16(value, i * 2).map(|o| (o & 0x7fff) as usize))
+        .collect();
+
+    offsets
+        .iter()
+        .enumerate()
+        .filter_map(|(i, start)| {
+            let end = offsets.get(i + 1).copied().unwrap_or(value.len());
+            value.get(*start..end).map(|v| v.to_vec())
+        })
+        .collect()
+}
+
+fn convert_value(column: &ColumnDefinition, value: &[u8]) -> Option<EseValue> {
+    Some(match column.column_type {
+        COLUMN_TYPE_BIT => EseValue::Bit(*value.first()? != 0),
+        COLUMN_TYPE_UNSIGNED_BYTE => EseValue::UnsignedByte(*value.first()?),
+        COLUMN_TYPE_SHORT => EseValue::Short(i16::from_le_bytes(value.get(..2)?.try_into().ok()?)),
+        COLUMN_TYPE_UNSIGNED_SHORT => {
+            EseValue::UnsignedShort(u16::from_le_bytes(value.get(..2)?.try_into().ok()?))
+        }
+        COLUMN_TYPE_LONG => EseValue::Long(i32::from_le_bytes(value.get(..4)?.try_into().ok()?)),
+        COLUMN_TYPE_UNSIGNED_LONG => {
+            EseValue::UnsignedLong(u32::from_le_bytes(value.get(..4)?.try_into().ok()?))
+        }
+        COLUMN_TYPE_LONG_LONG => {
+            EseValue::LongLong(i64::from_le_bytes(value.get(..8)?.try_into().ok()?))
+        }
+        COLUMN_TYPE_CURRENCY => {
+            EseValue::Currency(i64::from_le_bytes(value.get(..8)?.try_into().ok()?))
+        }
+        COLUMN_TYPE_IEEE_SINGLE => {
+            EseValue::Single(f32::from_le_bytes(value.get(..4)?.try_into().ok()?))
+        }
+        COLUMN_TYPE_IEEE_DOUBLE => {
+            EseValue::Double(f64::from_le_bytes(value.get(..8)?.try_into().ok()?))
+        }
+        COLUMN_TYPE_DATE_TIME => {
+            EseValue::DateTime(f64::from_le_bytes(value.get(..8)?.try_into().ok()?))
+        }
+        COLUMN_TYPE_GUID => EseValue::Guid(value.get(..16)?.try_into().ok()?),
+        COLUMN_TYPE_TEXT | COLUMN_TYPE_LONG_TEXT => EseValue::Text(decode_text(column, value)),
+        COLUMN_TYPE_BINARY | COLUMN_TYPE_LONG_BINARY => EseValue::Binary(value.to_vec()),
+        _ => EseValue::Binary(value.to_vec()),
+    })
+}
+
+fn decode_text(column: &ColumnDefinition, value: &[u8]) -> String {
+    if column.codepage == CODEPAGE_UNICODE {
+        let utf16: Vec<u16> = value
+            .chunks_exact(2)
+            .map(|chunk| u16::from_le_bytes([chunk[0], chunk[1]]))
+            .collect();
+
+        String::from_utf16_lossy(&utf16)
+            .trim_end_matches('\0')
+            .to_string()
+    } else {
+        value
+            .iter()
+            .map(|b| *b as char)
+            .collect::<String>()
+            .trim_end_matches('\0')
+            .to_string()
+    }
+}
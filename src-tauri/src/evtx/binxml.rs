@@ -0,0 +1,664 @@
+use crate::utils::{
+    filetime_to_string, guid_bytes_to_string, sid_bytes_to_string, utf16_bytes_to_string,
+};
+
+const TOKEN_EOF: u8 = 0x00;
+const TOKEN_OPEN_START_ELEMENT: u8 = 0x01;
+const TOKEN_CLOSE_START_ELEMENT: u8 = 0x02;
+const TOKEN_CLOSE_EMPTY_ELEMENT: u8 = 0x03;
+const TOKEN_END_ELEMENT: u8 = 0x04;
+const TOKEN_VALUE: u8 = 0x05;
+const TOKEN_ATTRIBUTE: u8 = 0x06;
+const TOKEN_CDATA_SECTION: u8 = 0x07;
+const TOKEN_CHAR_REFERENCE: u8 = 0x08;
+const TOKEN_ENTITY_REFERENCE: u8 = 0x09;
+const TOKEN_TEMPLATE_INSTANCE: u8 = 0x0c;
+const TOKEN_NORMAL_SUBSTITUTION: u8 = 0x0d;
+const TOKEN_OPTIONAL_SUBSTITUTION: u8 = 0x0e;
+const TOKEN_FRAGMENT_HEADER: u8 = 0x0f;
+
+const TOKEN_FLAG_MORE: u8 = 0x40;
+
+const VALUE_TYPE_NULL: u8 = 0x00;
+const VALUE_TYPE_BINXML: u8 = 0x21;
+const VALUE_TYPE_ARRAY: u8 = 0x80;
+
+// шаблоны и вложенные фрагменты могут ссылаться сами на себя
+const MAX_DEPTH: usize = 64;
+
+#[derive(Debug, Clone, Default)]
+pub struct Element {
+    pub name: String,
+    pub attributes: Vec<(String, String)>,
+    pub children: Vec<Element>,
+    pub text: String,
+}
+
+impl Element {
+    pub fn child(&self, name: &str) -> Option<&Element> {
+        self.children.iter().find(|c| c.name.eq(name))
+    }
+
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(n, _)| n.eq(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+// значение подстановки шаблона, хранится как ссылка на данные внутри чанка
+#[derive(Debug, Clone, Copy)]
+struct Substitution {
+    value_type: u8,
+    offset: usize,
+    size: usize,
+}
+
+// https://github.com/libyal/libevtx/blob/main/documentation/Windows%20XML%20Event%20Log%20(EVTX).asciidoc#binary-xml
+pub struct BinXmlParser<'a> {
+    chunk: &'a [u8],
+}
+
+impl<'a> BinXmlParser<'a> {
+    pub fn new(chunk: &'a [u8]) -> Self {
+        Self { chunk }
+    }
+
+    pub fn parse(&self, offset: usize, size: usize) -> Option<Element> {
+        let mut pos = offset;
+        self.parse_fragment(&mut pos, offset + size, &[], 0)
+            .into_iter()
+            .next()
+    }
+
+    fn parse_fragment(
+        &self,
+        pos: &mut usize,
+        end: usize,
+        subs: &[Substitution],
+        depth: usize,
+    ) -> Vec<Element> {
+        let mut elements = vec![];
+
+        if depth > MAX_DEPTH {
+            return elements;
+        }
+
+        while *pos < end {
+            let Some(token) = self.chunk.get(*pos) else {
+                break;
+            };
+
+            match token & 0x0f {
+                TOKEN_FRAGMENT_HEADER => *pos += 4,
+                TOKEN_TEMPLATE_INSTANCE => match self.parse_template(pos, depth + 1) {
+                    Some(mut items) => elements.append(&mut items),
+                    None => break,
+                },
+                TOKEN_OPEN_START_ELEMENT => match self.parse_element(pos, subs, depth + 1) {
+                    Some(element) => elements.push(element),
+                    None => break,
+                },
+                _ => break,
+            }
+        }
+
+        elements
+    }
+
+    fn parse_template(&self, pos: &mut usize, depth: usize) -> Option<Vec<Element>> {
+        // токен, неизвестный байт и идентификатор шаблона
+        *pos += 6;
+
+        let definition_offset = self.u32(*pos)? as usize;
+        *pos += 4;
+
+        // определение шаблона лежит сразу за ссылкой, если встречается в чанке впервые
+        let definition_size = self.u32(definition_offset + 20)? as usize;
+        let definition_data = definition_offset + 24;
+
+        if definition_offset == *pos {
+            *pos = definition_data + definition_size;
+        }
+
+        let count = self.u32(*pos)? as usize;
+        *pos += 4;
+
+        // на каждую подстановку приходится минимум 4 байта описателя
+        if count > self.chunk.len().saturating_sub(*pos) / 4 {
+            return None;
+        }
+
+        let mut subs = Vec::with_capacity(count);
+        let mut value_offset = *pos + count * 4;
+
+        for index in 0..count {
+            let size = self.u16(*pos + index * 4)? as usize;
+            let value_type = *self.chunk.get(*pos + index * 4 + 2)?;
+
+            subs.push(Substitution {
+                value_type,
+                offset: value_offset,
+                size,
+            });
+
+            value_offset += size;
+        }
+
+        *pos = value_offset;
+
+        let mut definition_pos = definition_data;
+        Some(self.parse_fragment(
+            &mut definition_pos,
+            definition_data + definition_size,
+            &subs,
+            depth + 1,
+        ))
+    }
+
+    fn parse_element(
+        &self,
+        pos: &mut usize,
+        subs: &[Substitution],
+        depth: usize,
+    ) -> Option<Element> {
+        if depth > MAX_DEPTH {
+            return None;
+        }
+
+        let token = *self.chunk.get(*pos)?;
+        // токен, идентификатор зависимости и размер данных
+        *pos += 7;
+
+        let mut element = Element {
+            name: self.read_name(pos)?,
+            ..Default::default()
+        };
+
+        if token & TOKEN_FLAG_MORE != 0 {
+            *pos += 4;
+
+            while let Some(token) = self.chunk.get(*pos) {
+                if token & 0x0f != TOKEN_ATTRIBUTE {
+                    break;
+                }
+
+                *pos += 1;
+                let name = self.read_name(pos)?;
+                let mut value = String::new();
+
+                loop {
+                    let token = *self.chunk.get(*pos)?;
+
+                    match token & 0x0f {
+                        TOKEN_VALUE => value.push_str(&self.read_value_token(pos)?),
+                        TOKEN_NORMAL_SUBSTITUTION | TOKEN_OPTIONAL_SUBSTITUTION => {
+                            if let Some(sub) = self.read_substitution(pos, subs)? {
+                                value.push_str(&self.render(&sub));
+                            }
+                        }
+                        TOKEN_CHAR_REFERENCE => {
+                            *pos += 1;
+                            if let Some(c) = char::from_u32(self.u16(*pos)? as u32) {
+                                value.push(c);
+                            }
+                            *pos += 2;
+                        }
+                        TOKEN_ENTITY_REFERENCE => {
+                            *pos += 1;
+                            value.push_str(&format!("&{};", self.read_name(pos)?));
+                        }
+                        _ => break,
+                    }
+                }
+
+                element.attributes.push((name, value));
+            }
+        }
+
+        match *self.chunk.get(*pos)? {
+            TOKEN_CLOSE_EMPTY_ELEMENT => {
+                *pos += 1;
+                return Some(element);
+            }
+            TOKEN_CLOSE_START_ELEMENT => *pos += 1,
+            _ => return None,
+        }
+
+        loop {
+            let token = *self.chunk.get(*pos)?;
+
+            match token & 0x0f {
+                TOKEN_END_ELEMENT => {
+                    *pos += 1;
+                    break;
+                }
+                TOKEN_OPEN_START_ELEMENT => {
+                    element
+                        .children
+                        .push(self.parse_element(pos, subs, depth + 1)?)
+                }
+                TOKEN_VALUE => element.text.push_str(&self.read_value_token(pos)?),
+                TOKEN_CDATA_SECTION => {
+                    *pos += 1;
+                    let count = self.u16(*pos)? as usize;
+                    *pos += 2;
+                    element.text.push_str(&utf16_bytes_to_string(
+                        self.chunk.get(*pos..*pos + count * 2)?,
+                    ));
+                    *pos += count * 2;
+                }
+                TOKEN_CHAR_REFERENCE => {
+                    *pos += 1;
+                    if let Some(c) = char::from_u32(self.u16(*pos)? as u32) {
+                        element.text.push(c);
+                    }
+                    *pos += 2;
+                }
+                TOKEN_ENTITY_REFERENCE => {
+                    *pos += 1;
+                    element
+                        .text
+                        .push_str(&format!("&{};", self.read_name(pos)?));
+                }
+                TOKEN_NORMAL_SUBSTITUTION | TOKEN_OPTIONAL_SUBSTITUTION => {
+                    if let Some(sub) = self.read_substitution(pos, subs)? {
+                        if sub.value_type == VALUE_TYPE_BINXML {
+                            let mut nested = sub.offset;
+                            element.children.extend(self.parse_fragment(
+                                &mut nested,
+                                sub.offset + sub.size,
+                                &[],
+                                depth + 1,
+                            ));
+                        } else {
+                            element.text.push_str(&self.render(&sub));
+                        }
+                    }
+                }
+                TOKEN_TEMPLATE_INSTANCE => element
+                    .children
+                    .extend(self.parse_template(pos, depth + 1)?),
+                TOKEN_EOF => break,
+                _ => return None,
+            }
+        }
+
+        Some(element)
+    }
+
+    // имя хранится один раз в чанке, повторные вхождения ссылаются на первое
+    fn read_name(&self, pos: &mut usize) -> Option<String> {
+        let offset = self.u32(*pos)? as usize;
+        *pos += 4;
+
+        let count = self.u16(offset + 6)? as usize;
+        let name = utf16_bytes_to_string(self.chunk.get(offset + 8..offset + 8 + count * 2)?);
+
+        if offset == *pos {
+            *pos += 10 + count * 2;
+        }
+
+        Some(name)
+    }
+
+    fn read_value_token(&self, pos: &mut usize) -> Option<String> {
+        // токен и тип значения, в тексте всегда строка
+        *pos += 2;
+        let count = self.u16(*pos)? as usize;
+        *pos += 2;
+
+        let value = utf16_bytes_to_string(self.chunk.get(*pos..*pos + count * 2)?);
+        *pos += count * 2;
+
+        Some(value)
+    }
+
+    fn read_substitution(
+        &self,
+        pos: &mut usize,
+        subs: &[Substitution],
+    ) -> Option<Option<Substitution>> {
+        let id = self.u16(*pos + 1)? as usize;
+        *pos += 4;
+
+        Some(
+            subs.get(id)
+                .copied()
+                .filter(|sub| sub.value_type != VALUE_TYPE_NULL && sub.size > 0),
+        )
+    }
+
+    fn render(&self, sub: &Substitution) -> String {
+        let Some(data) = self.chunk.get(sub.offset..sub.offset + sub.size) else {
+            return String::new();
+        };
+
+        if sub.value_type & VALUE_TYPE_ARRAY != 0 {
+            return render_array(sub.value_type & !VALUE_TYPE_ARRAY, data);
+        }
+
+        render_value(sub.value_type, data)
+    }
+
+    fn u16(&self, offset: usize) -> Option<u16> {
+        Some(u16::from_le_bytes(
+            self.chunk.get(offset..offset + 2)?.try_into().ok()?,
+        ))
+    }
+
+    fn u32(&self, offset: usize) -> Option<u32> {
+        Some(u32::from_le_bytes(
+            self.chunk.get(offset..offset + 4)?.try_into().ok()?,
+        ))
+    }
+}
+
+fn render_array(value_type: u8, data: &[u8]) -> String {
+    match value_type {
+        0x01 => utf16_bytes_to_string(data)
+            .split('\0')
+            .filter(|s| !s.is_empty())
+            .collect::<Vec<_>>()
+            .join(", "),
+        _ => {
+            let size = match value_type {
+                0x03 | 0x04 => 1,
+                0x05 | 0x06 => 2,
+                0x07 | 0x08 | 0x0b | 0x0d | 0x14 => 4,
+                0x09 | 0x0a | 0x0c | 0x11 | 0x15 => 8,
+                0x0f | 0x12 => 16,
+                _ => return hex::encode_upper(data),
+            };
+
+            data.chunks_exact(size)
+                .map(|chunk| render_value(value_type, chunk))
+                .collect::<Vec<_>>()
+                .join(", ")
+        }
+    }
+}
+
+fn render_value(value_type: u8, data: &[u8]) -> String {
+    fn le<const N: usize>(data: &[u8]) -> [u8; N] {
+        let mut buf = [0u8; N];
+        let len = data.len().min(N);
+        buf[..len].copy_from_slice(&data[..len]);
+        buf
+    }
+
+    match value_type {
+        0x01 => utf16_bytes_to_string(data),
+        0x02 => data
+            .iter()
+            .map(|b| *b as char)
+            .collect::<String>()
+            .trim_end_matches('\0')
+            .to_string(),
+        0x03 => (data[0] as i8).to_string(),
+        0x04 => data[0].to_string(),
+        0x05 => i16::from_le_bytes(le(data)).to_string(),
+        0x06 => u16::from_le_bytes(le(data)).to_string(),
+        0x07 => i32::from_le_bytes(le(data)).to_string(),
+        0x08 => u32::from_le_bytes(le(data)).to_string(),
+        0x09 => i64::from_le_bytes(le(data)).to_string(),
+        0x0a => u64::from_le_bytes(le(data)).to_string(),
+        0x0b => f32::from_le_bytes(le(data)).to_string(),
+        0x0c => f64::from_le_bytes(le(data)).to_string(),
+        0x0d => (u32::from_le_bytes(le(data)) != 0).to_string(),
+        0x0f => guid_bytes_to_string(&le(data)),
+        0x10 => {
+            if data.len() == 8 {
+                format!("0x{:x}", u64::from_le_bytes(le(data)))
+            } else {
+                format!("0x{:x}", u32::from_le_bytes(le(data)))
+            }
+        }
+        0x11 => filetime_to_string(i64::from_le_bytes(le(data))),
+        0x12 => {
+            let part = |i: usize| u16::from_le_bytes(le(&data[(i * 2).min(data.len())..]));
+            format!(
+                "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+                part(0),
+                part(1),
+                part(3),
+                part(4),
+                part(5),
+                part(6)
+            )
+        }
+        0x13 => sid_bytes_to_string(data).unwrap_or_default(),
+        0x14 => format!("0x{:x}", u32::from_le_bytes(le(data))),
+        0x15 => format!("0x{:x}", u64::from_le_bytes(le(data))),
+        _ => hex::encode_upper(data),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Builder {
+        data: Vec<u8>,
+    }
+
+    impl Builder {
+        fn u8(&mut self, value: u8) {
+            self.data.push(value);
+        }
+
+        fn u16(&mut self, value: u16) {
+            self.data.extend(value.to_le_bytes());
+        }
+
+        fn u32(&mut self, value: u32) {
+            self.data.extend(value.to_le_bytes());
+        }
+
+        fn position(&self) -> u32 {
+            self.data.len() as u32
+        }
+
+        fn utf16(&mut self, value: &str) {
+            value.encode_utf16().for_each(|c| self.u16(c));
+        }
+
+        // имя записано сразу за ссылкой на него
+        fn name(&mut self, name: &str) {
+            self.u32(self.position() + 4);
+            self.u32(0);
+            self.u16(0);
+            self.u16(name.len() as u16);
+            self.utf16(name);
+            self.u16(0);
+        }
+
+        fn open(&mut self, name: &str, attributes: bool) {
+            self.u8(if attributes {
+                TOKEN_OPEN_START_ELEMENT | TOKEN_FLAG_MORE
+            } else {
+                TOKEN_OPEN_START_ELEMENT
+            });
+            self.u16(0);
+            self.u32(0);
+            self.name(name);
+
+            if attributes {
+                self.u32(0);
+            }
+        }
+
+        fn substitution(&mut self, id: u16, value_type: u8) {
+            self.u8(TOKEN_NORMAL_SUBSTITUTION);
+            self.u16(id);
+            self.u8(value_type);
+        }
+
+        fn fragment(&mut self) {
+            self.data.extend([TOKEN_FRAGMENT_HEADER, 1, 1, 0]);
+        }
+    }
+
+    // <Event><System><Provider Name="%1"/><EventID>%0</EventID></System>
+    // <EventData><Data Name="Foo">%2</Data></EventData></Event>
+    #[test]
+    fn parses_template_instance() {
+        let mut b = Builder { data: vec![0; 512] };
+        let start = b.position();
+
+        b.fragment();
+        b.u8(TOKEN_TEMPLATE_INSTANCE);
+        b.u8(1);
+        b.u32(1);
+        b.u32(b.position() + 4);
+        b.u32(0);
+        b.data.extend([0; 16]);
+
+        let size_position = b.data.len();
+        b.u32(0);
+        let definition_start = b.position();
+
+        b.fragment();
+        b.open("Event", false);
+        b.u8(TOKEN_CLOSE_START_ELEMENT);
+        b.open("System", false);
+        b.u8(TOKEN_CLOSE_START_ELEMENT);
+        b.open("Provider", true);
+        b.u8(TOKEN_ATTRIBUTE);
+        b.name("Name");
+        b.substitution(1, 1);
+        b.u8(TOKEN_CLOSE_EMPTY_ELEMENT);
+        b.open("EventID", false);
+        b.u8(TOKEN_CLOSE_START_ELEMENT);
+        b.substitution(0, 6);
+        b.u8(TOKEN_END_ELEMENT);
+        b.u8(TOKEN_END_ELEMENT);
+        b.open("EventData", false);
+        b.u8(TOKEN_CLOSE_START_ELEMENT);
+        b.open("Data", true);
+        b.u8(TOKEN_ATTRIBUTE);
+        b.name("Name");
+        b.u8(TOKEN_VALUE);
+        b.u8(1);
+        b.u16(3);
+        b.utf16("Foo");
+        b.u8(TOKEN_CLOSE_START_ELEMENT);
+        b.substitution(2, 1);
+        b.u8(TOKEN_END_ELEMENT);
+        b.u8(TOKEN_END_ELEMENT);
+        b.u8(TOKEN_END_ELEMENT);
+        b.u8(TOKEN_EOF);
+
+        let definition_size = b.position() - definition_start;
+        b.data[size_position..size_position + 4].copy_from_slice(&definition_size.to_le_bytes());
+
+        // описатели подстановок (размер, тип), затем сами значения
+        let provider: Vec<u8> = "Security"
+            .encode_utf16()
+            .flat_map(u16::to_le_bytes)
+            .collect();
+        let path: Vec<u8> = "C:\\x.exe"
+            .encode_utf16()
+            .flat_map(u16::to_le_bytes)
+            .collect();
+
+        b.u32(3);
+        b.u16(2);
+        b.u16(6);
+        b.u16(provider.len() as u16);
+        b.u16(1);
+        b.u16(path.len() as u16);
+        b.u16(1);
+        b.u16(4688);
+        b.data.extend(&provider);
+        b.data.extend(&path);
+        b.u8(TOKEN_EOF);
+
+        let end = b.position();
+        let root = BinXmlParser::new(&b.data)
+            .parse(start as usize, (end - start) as usize)
+            .unwrap();
+
+        assert_eq!(root.name, "Event");
+
+        let system = root.child("System").unwrap();
+        assert_eq!(system.child("EventID").unwrap().text, "4688");
+        assert_eq!(
+            system.child("Provider").unwrap().attribute("Name"),
+            Some("Security")
+        );
+
+        let data = &root.child("EventData").unwrap().children[0];
+        assert_eq!(data.attribute("Name"), Some("Foo"));
+        assert_eq!(data.text, "C:\\x.exe");
+    }
+
+    // ссылка на шаблон с определением, записанным сразу за ней
+    fn template(b: &mut Builder, body: impl FnOnce(&mut Builder, u32)) {
+        b.u8(TOKEN_TEMPLATE_INSTANCE);
+        b.u8(1);
+        b.u32(1);
+
+        let definition = b.position() + 4;
+        b.u32(definition);
+        b.u32(0);
+        b.data.extend([0; 16]);
+
+        let size_position = b.data.len();
+        b.u32(0);
+        let definition_start = b.position();
+
+        body(b, definition);
+
+        let definition_size = b.position() - definition_start;
+        b.data[size_position..size_position + 4].copy_from_slice(&definition_size.to_le_bytes());
+    }
+
+    #[test]
+    fn stops_on_self_referencing_template() {
+        let mut b = Builder { data: vec![0; 512] };
+        let start = b.position();
+
+        b.fragment();
+        template(&mut b, |b, definition| {
+            b.fragment();
+            b.u8(TOKEN_TEMPLATE_INSTANCE);
+            b.u8(1);
+            b.u32(1);
+            b.u32(definition);
+            b.u32(0);
+            b.u8(TOKEN_EOF);
+        });
+        b.u32(0);
+        b.u8(TOKEN_EOF);
+
+        let end = b.position();
+        let parser = BinXmlParser::new(&b.data);
+
+        assert!(parser
+            .parse(start as usize, (end - start) as usize)
+            .is_none());
+    }
+
+    #[test]
+    fn rejects_oversized_substitution_count() {
+        let mut b = Builder { data: vec![0; 512] };
+        let start = b.position();
+
+        b.fragment();
+        template(&mut b, |b, _| {
+            b.fragment();
+            b.u8(TOKEN_EOF);
+        });
+        b.u32(u32::MAX);
+        b.u8(TOKEN_EOF);
+
+        let end = b.position();
+        let parser = BinXmlParser::new(&b.data);
+
+        assert!(parser
+            .parse(start as usize, (end - start) as usize)
+            .is_none());
+    }
+}
@@ -0,0 +1,159 @@
+use std::path::Path;
+
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use serde::{Deserialize, Serialize};
+
+use super::evtx::{Evtx, EvtxEvent};
+
+pub const LOGS_DIR: &str = "C:\\Windows\\System32\\winevt\\Logs";
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct EventDat {
+    pub timestamp: i64,
+    pub log: String,
+    pub event_id: u32,
+    pub description: String,
+    pub details: String,
+}
+
+type Extractor = fn(&EvtxEvent) -> Option<(&'static str, String)>;
+
+const LOGS: [(&str, &str, Extractor); 4] = [
+    ("Security", "Security.evtx", extract_security),
+    ("System", "System.evtx", extract_system),
+    (
+        "Defender",
+        "Microsoft-Windows-Windows Defender%4Operational.evtx",
+        extract_defender,
+    ),
+    (
+        "Kernel-PnP",
+        "Microsoft-Windows-Kernel-PnP%4Configuration.evtx",
+        extract_pnp,
+    ),
+];
+
+pub fn collect_events() -> Vec<EventDat> {
+    collect_events_from(LOGS_DIR)
+}
+
+pub fn collect_events_from(dir: &str) -> Vec<EventDat> {
+    let mut response: Vec<EventDat> = LOGS
+        .par_iter()
+        .flat_map(|(log, file, extract)| {
+            let path = Path::new(dir).join(file);
+
+            match Evtx::open(&path.to_string_lossy()) {
+                Some(evtx) => evtx
+                    .events()
+                    .iter()
+                    .filter_map(|event| {
+                        extract(event).map(|(description, details)| EventDat {
+                            timestamp: event.timestamp,
+                            log: log.to_string(),
+                            event_id: event.event_id,
+                            description: description.to_string(),
+                            details,
+                        })
+                    })
+                    .collect(),
+                None => vec![],
+            }
+        })
+        .collect();
+
+    response.sort_by_key(|event| event.timestamp);
+    response
+}
+
+fn extract_security(event: &EvtxEvent) -> Option<(&'static str, String)> {
+    match event.event_id {
+        4688 => Some((
+            "создание процесса",
+            format!(
+                "{} {} | родитель: {} | пользователь: {}",
+                event.get("NewProcessName"),
+                event.get("CommandLine"),
+                event.get("ParentProcessName"),
+                event.get("SubjectUserName")
+            ),
+        )),
+        1102 => Some((
+            "журнал безопасности очищен",
+            format!("пользователь: {}", event.get("SubjectUserName")),
+        )),
+        4616 => Some((
+            "изменено системное время",
+            format!(
+                "{} -> {} | процесс: {} | пользователь: {}",
+                event.get("PreviousTime"),
+                event.get("NewTime"),
+                event.get("ProcessName"),
+                event.get("SubjectUserName")
+            ),
+        )),
+        _ => None,
+    }
+}
+
+fn extract_system(event: &EvtxEvent) -> Option<(&'static str, String)> {
+    match (event.event_id, event.provider.as_str()) {
+        (7045, "Service Control Manager") => Some((
+            "установлена служба",
+            format!(
+                "{} | {} | тип: {} | запуск: {} | учетная запись: {}",
+                event.get("ServiceName"),
+                event.get("ImagePath"),
+                event.get("ServiceType"),
+                event.get("StartType"),
+                event.get("AccountName")
+            ),
+        )),
+        (104, "Microsoft-Windows-Eventlog") => Some((
+            "журнал очищен",
+            format!(
+                "журнал: {} | пользователь: {}",
+                event.get("Channel"),
+                event.get("SubjectUserName")
+            ),
+        )),
+        _ => None,
+    }
+}
+
+fn extract_defender(event: &EvtxEvent) -> Option<(&'static str, String)> {
+    let description = match event.event_id {
+        1116 => "обнаружена угроза",
+        1117 => "выполнено действие над угрозой",
+        _ => return None,
+    };
+
+    Some((
+        description,
+        format!(
+            "{} | {} | действие: {} | пользователь: {}",
+            event.get("Threat Name"),
+            event.get("Path"),
+            event.get("Action Name"),
+            event.get("Detection User")
+        ),
+    ))
+}
+
+fn extract_pnp(event: &EvtxEvent) -> Option<(&'static str, String)> {
+    let description = match event.event_id {
+        400 => "устройство настроено",
+        410 => "устройство запущено",
+        420 => "устройство удалено",
+        _ => return None,
+    };
+
+    Some((
+        description,
+        format!(
+            "{} | драйвер: {}",
+            event.get("DeviceInstanceId"),
+            event.get("DriverName")
+        ),
+    ))
+}
@@ -0,0 +1,176 @@
+use std::fs;
+
+use serde::{Deserialize, Serialize};
+
+use super::binxml::{BinXmlParser, Element};
+
+const FILE_SIGNATURE: &[u8] = b"ElfFile\0";
+const CHUNK_SIGNATURE: &[u8] = b"ElfChnk\0";
+const RECORD_SIGNATURE: u32 = 0x00002a2a;
+
+const FILE_HEADER_SIZE: usize = 4096;
+const CHUNK_SIZE: usize = 65536;
+const CHUNK_HEADER_SIZE: usize = 512;
+const RECORD_HEADER_SIZE: usize = 24;
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct EvtxEvent {
+    pub record_id: u64,
+    pub timestamp: i64,
+    pub event_id: u32,
+    pub provider: String,
+    pub channel: String,
+    pub computer: String,
+    pub data: Vec<(String, String)>,
+}
+
+impl EvtxEvent {
+    pub fn get(&self, name: &str) -> &str {
+        self.data
+            .iter()
+            .find(|(n, _)| n.eq(name))
+            .map(|(_, v)| v.as_str())
+            .unwrap_or("")
+    }
+
+    fn from_element(record_id: u64, timestamp: i64, root: &Element) -> Option<Self> {
+        let system = root.child("System")?;
+
+        let mut data = vec![];
+
+        if let Some(event_data) = root.child("EventData") {
+            for (index, item) in event_data.children.iter().enumerate() {
+                let name = item
+                    .attribute("Name")
+                    .map(|n| n.to_string())
+                    .unwrap_or_else(|| format!("Data{}", index));
+
+                data.push((name, item.text.clone()));
+            }
+        }
+
+        // в UserData лежит произвольный элемент, значения берем из его потомков
+        if let Some(user_data) = root.child("UserData") {
+            for item in user_data.children.iter().flat_map(|c| c.children.iter()) {
+                data.push((item.name.clone(), item.text.clone()));
+            }
+        }
+
+        Some(Self {
+            record_id,
+            timestamp,
+            event_id: system.child("EventID")?.text.trim().parse().ok()?,
+            provider: system
+                .child("Provider")
+                .and_then(|p| p.attribute("Name"))
+                .unwrap_or("")
+                .to_string(),
+            channel: system
+                .child("Channel")
+                .map(|c| c.text.clone())
+                .unwrap_or_default(),
+            computer: system
+                .child("Computer")
+                .map(|c| c.text.clone())
+                .unwrap_or_default(),
+            data,
+        })
+    }
+}
+
+// https://github.com/libyal/libevtx/blob/main/documentation/Windows%20XML%20Event%20Log%20(EVTX).asciidoc
+pub struct Evtx {
+    data: Vec<u8>,
+}
+
+impl Evtx {
+    pub fn open(path: &str) -> Option<Self> {
+        match fs::read(path) {
+            Ok(data) => {
+                if !data.starts_with(FILE_SIGNATURE) {
+                    return None;
+                }
+
+                Some(Self { data })
+            }
+
+            Err(e) => {
+                if cfg!(dev) {
+                    println!("{e:?}");
+                }
+
+                None
+            }
+        }
+    }
+
+    // количество чанков в заголовке может быть устаревшим, если журнал не был корректно закрыт,
+    // поэтому проверяем каждый блок по сигнатуре
+    pub fn events(&self) -> Vec<EvtxEvent> {
+        let mut events = vec![];
+        let mut offset = FILE_HEADER_SIZE;
+
+        while offset + CHUNK_SIZE <= self.data.len() {
+            let chunk = &self.data[offset..offset + CHUNK_SIZE];
+
+            if chunk.starts_with(CHUNK_SIGNATURE) {
+                events.extend(read_chunk(chunk));
+            }
+
+            offset += CHUNK_SIZE;
+        }
+
+        events
+    }
+}
+
+fn read_chunk(chunk: &[u8]) -> Vec<EvtxEvent> {
+    let mut events = vec![];
+    let parser = BinXmlParser::new(chunk);
+
+    let free_space_offset = u32_at(chunk, 48)
+        .map(|v| v as usize)
+        .unwrap_or(CHUNK_SIZE)
+        .min(CHUNK_SIZE);
+    let mut offset = CHUNK_HEADER_SIZE;
+
+    while offset + RECORD_HEADER_SIZE < free_space_offset {
+        if u32_at(chunk, offset) != Some(RECORD_SIGNATURE) {
+            break;
+        }
+
+        let Some(size) = u32_at(chunk, offset + 4).map(|v| v as usize) else {
+            break;
+        };
+
+        if size < RECORD_HEADER_SIZE + 4 || offset + size > CHUNK_SIZE {
+            break;
+        }
+
+        let record_id = u64_at(chunk, offset + 8).unwrap_or(0);
+        let timestamp = u64_at(chunk, offset + 16).unwrap_or(0) as i64;
+
+        if let Some(root) = parser.parse(offset + RECORD_HEADER_SIZE, size - RECORD_HEADER_SIZE - 4)
+        {
+            if let Some(event) = EvtxEvent::from_element(record_id, timestamp, &root) {
+                events.push(event);
+            }
+        }
+
+        offset += size;
+    }
+
+    events
+}
+
+fn u32_at(data: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(
+        data.get(offset..offset + 4)?.try_into().ok()?,
+    ))
+}
+
+fn u64_at(data: &[u8], offset: usize) -> Option<u64> {
+    Some(u64::from_le_bytes(
+        data.get(offset..offset + 8)?.try_into().ok()?,
+    ))
+}
@@ -0,0 +1,3 @@
+pub mod binxml;
+pub mod events;
+pub mod evtx;
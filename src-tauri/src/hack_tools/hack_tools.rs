@@ -0,0 +1,413 @@
+use std::{collections::BTreeSet, fs, path::Path, time::UNIX_EPOCH};
+
+use jwalk::WalkDir;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use windows::Win32::System::Registry::HKEY_LOCAL_MACHINE;
+use windows_registry::LOCAL_MACHINE;
+
+use crate::{
+    profiles::user_hive::{collect_user_hives, UserHive},
+    utils::{
+        expand_environment_strings, filetime_to_unix, get_key_last_write_time, is_windows_path,
+        text_bytes_to_string, xml_unescape,
+    },
+};
+
+const CHEAT_ENGINE: &str = "Cheat Engine";
+const X64DBG: &str = "x64dbg";
+const RECLASS: &str = "ReClass";
+
+const CHEAT_ENGINE_KEY: &str = "Software\\Cheat Engine";
+
+const SERVICES_KEY: &str = "SYSTEM\\CurrentControlSet\\Services";
+// драйвер cheat engine регистрируется как служба CEDRIVER<версия> или под именем файла
+const CE_DRIVER_FILES: [&str; 2] = ["dbk64.sys", "dbk32.sys"];
+const CE_DRIVER_SERVICE: &str = "cedriver";
+
+// файлы настроек относительно AppData\Roaming
+const SETTINGS_FILES: [(&str, &str); 3] = [
+    ("Process Hacker", "Process Hacker 2\\settings.xml"),
+    ("System Informer", "SystemInformer\\settings.xml"),
+    (RECLASS, "ReClass.NET\\settings.xml"),
+];
+
+// папки профиля, куда обычно скачивают и распаковывают инструменты и таблицы
+const SEARCH_DIRS: [&str; 3] = ["Desktop", "Downloads", "Documents"];
+const SEARCH_DEPTH: usize = 4;
+
+const TOOL_EXECUTABLES: [(&str, &str); 7] = [
+    (CHEAT_ENGINE, "cheatengine"),
+    (X64DBG, "x64dbg"),
+    (X64DBG, "x32dbg"),
+    (X64DBG, "x96dbg"),
+    (RECLASS, "reclass"),
+    ("Process Hacker", "processhacker"),
+    ("System Informer", "systeminformer"),
+];
+
+const X64DBG_CONFIGS: [&str; 2] = ["x64dbg.ini", "x32dbg.ini"];
+const RECLASS_PROJECTS: [&str; 2] = [".rcnet", ".reclass"];
+
+const SETTINGS_PATH: &str = r">([A-Za-z]:\\[^<]+)<";
+const TABLE_PROCESS: &str = r"(?i)[\w\-.]+\.exe";
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct HackToolTrace {
+    pub user: String,
+    pub tool: &'static str,
+    pub source: String,
+    pub value: String,
+    pub timestamp: i64,
+}
+
+pub fn collect_hack_tool_traces() -> Vec<HackToolTrace> {
+    let mut traces = read_ce_driver_services();
+
+    for user in collect_user_hives() {
+        traces.extend(read_cheat_engine_settings(&user));
+        traces.extend(read_settings_files(&user));
+
+        for dir in SEARCH_DIRS {
+            traces.extend(search_dir(
+                &user.profile.name,
+                &Path::new(&user.profile.path).join(dir),
+            ));
+        }
+    }
+
+    // группируем по инструменту, внутри по времени
+    traces.sort_by(|a, b| a.tool.cmp(b.tool).then(a.timestamp.cmp(&b.timestamp)));
+
+    traces
+}
+
+fn read_ce_driver_services() -> Vec<HackToolTrace> {
+    let Ok(services) = LOCAL_MACHINE.open(SERVICES_KEY) else {
+        return vec![];
+    };
+
+    let Ok(names) = services.keys() else {
+        return vec![];
+    };
+
+    names
+        .filter_map(|name| {
+            let image_path = services
+                .open(&name)
+                .ok()?
+                .get_string("ImagePath")
+                .unwrap_or_default();
+
+            let file_name = image_path
+                .rsplit('\\')
+                .next()
+                .unwrap_or_default()
+                .to_lowercase();
+
+            if !name.to_lowercase().starts_with(CE_DRIVER_SERVICE)
+                && !CE_DRIVER_FILES.contains(&file_name.as_str())
+            {
+                return None;
+            }
+
+            let path = format!("{}\\{}", SERVICES_KEY, name);
+
+            Some(HackToolTrace {
+                user: String::new(),
+                tool: CHEAT_ENGINE,
+                timestamp: filetime_to_unix(get_key_last_write_time(HKEY_LOCAL_MACHINE, &path)),
+                source: format!("HKLM\\{}", path),
+                value: format!("служба драйвера {}", image_path),
+            })
+        })
+        .collect()
+}
+
+fn read_cheat_engine_settings(user: &UserHive) -> Vec<HackToolTrace> {
+    let Some(key) = user.open(CHEAT_ENGINE_KEY) else {
+        return vec![];
+    };
+
+    let timestamp = filetime_to_unix(user.last_write(CHEAT_ENGINE_KEY));
+    let source = format!("HKU\\{}\\{}", user.profile.name, CHEAT_ENGINE_KEY);
+
+    let trace = |value: String| HackToolTrace {
+        user: user.profile.name.clone(),
+        tool: CHEAT_ENGINE,
+        source: source.clone(),
+        value,
+        timestamp,
+    };
+
+    // сам раздел говорит о том, что cheat engine запускался под этим пользователем
+    let mut traces = vec![trace(String::from("настройки Cheat Engine"))];
+
+    // недавние таблицы, папки и процессы хранятся в строковых значениях
+    traces.extend(key.values().into_iter().filter_map(|value| {
+        let data = value.as_string()?;
+        let data = data.trim();
+
+        (is_windows_path(data) || data.to_lowercase().ends_with(".exe"))
+            .then(|| trace(format!("{}: {}", value.name, data)))
+    }));
+
+    traces
+}
+
+fn read_settings_files(user: &UserHive) -> Vec<HackToolTrace> {
+    let Ok(path_pattern) = Regex::new(SETTINGS_PATH) else {
+        return vec![];
+    };
+
+    SETTINGS_FILES
+        .iter()
+        .flat_map(|(tool, file)| {
+            let path = user.profile.app_data().join(file);
+
+            let Ok(data) = fs::read(&path) else {
+                return vec![];
+            };
+
+            let text = text_bytes_to_string(&data);
+            let trace = |value: String| HackToolTrace {
+                user: user.profile.name.clone(),
+                tool,
+                source: path.to_string_lossy().to_string(),
+                value,
+                timestamp: modified_time(&path),
+            };
+
+            let mut traces = vec![trace(String::from("файл настроек"))];
+
+            // последние открытые файлы и папки
+            traces.extend(
+                path_pattern
+                    .captures_iter(&text)
+                    .map(|captures| expand_environment_strings(&xml_unescape(captures[1].trim())))
+                    .collect::<BTreeSet<String>>()
+                    .into_iter()
+                    .map(trace),
+            );
+
+            traces
+        })
+        .collect()
+}
+
+fn search_dir(user: &str, dir: &Path) -> Vec<HackToolTrace> {
+    WalkDir::new(dir)
+        .max_depth(SEARCH_DEPTH)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type.is_file())
+        .flat_map(|entry| {
+            let path = entry.path();
+            let name = entry.file_name.to_string_lossy().to_lowercase();
+
+            let (tool, values) = match classify_file(&name) {
+                Some(FileKind::CheatTable) => (CHEAT_ENGINE, read_cheat_table(&path)),
+                Some(FileKind::DebuggerConfig) => (X64DBG, read_debugger_config(&path)),
+                Some(FileKind::Other(tool, value)) => (tool, vec![value]),
+                None => return vec![],
+            };
+
+            let timestamp = modified_time(&path);
+
+            values
+                .into_iter()
+                .map(|value| HackToolTrace {
+                    user: user.to_string(),
+                    tool,
+                    source: path.to_string_lossy().to_string(),
+                    value,
+                    timestamp,
+                })
+                .collect()
+        })
+        .collect()
+}
+
+enum FileKind {
+    CheatTable,
+    DebuggerConfig,
+    Other(&'static str, String),
+}
+
+fn classify_file(name: &str) -> Option<FileKind> {
+    if name.ends_with(".ct") {
+        return Some(FileKind::CheatTable);
+    }
+
+    if X64DBG_CONFIGS.contains(&name) {
+        return Some(FileKind::DebuggerConfig);
+    }
+
+    if CE_DRIVER_FILES.contains(&name) {
+        return Some(FileKind::Other(
+            CHEAT_ENGINE,
+            String::from("файл драйвера Cheat Engine"),
+        ));
+    }
+
+    if RECLASS_PROJECTS.iter().any(|ext| name.ends_with(ext)) {
+        return Some(FileKind::Other(RECLASS, String::from("проект ReClass")));
+    }
+
+    TOOL_EXECUTABLES
+        .iter()
+        .find(|(_, prefix)| name.starts_with(prefix) && name.ends_with(".exe"))
+        .map(|(tool, _)| FileKind::Other(tool, String::from("исполняемый файл")))
+}
+
+fn read_cheat_table(path: &Path) -> Vec<String> {
+    let data = match fs::read(path) {
+        Ok(data) => data,
+        Err(e) => {
+            if cfg!(dev) {
+                println!("{e:?}");
+            }
+
+            return vec![];
+        }
+    };
+
+    let processes = parse_cheat_table(&text_bytes_to_string(&data));
+
+    vec![match processes.is_empty() {
+        true => String::from("таблица Cheat Engine"),
+        false => format!("таблица Cheat Engine для {}", processes.join(", ")),
+    }]
+}
+
+// целевой процесс встречается в адресах вида "cs2.exe"+1234 и в lua скриптах
+pub fn parse_cheat_table(text: &str) -> Vec<String> {
+    let Ok(process) = Regex::new(TABLE_PROCESS) else {
+        return vec![];
+    };
+
+    process
+        .find_iter(text)
+        .map(|process| process.as_str().to_lowercase())
+        .collect::<BTreeSet<String>>()
+        .into_iter()
+        .collect()
+}
+
+fn read_debugger_config(path: &Path) -> Vec<String> {
+    let Ok(data) = fs::read(path) else {
+        return vec![];
+    };
+
+    let mut values = vec![String::from("файл настроек")];
+    values.extend(
+        parse_recent_ini(&text_bytes_to_string(&data))
+            .into_iter()
+            .map(|file| format!("недавний файл {}", file)),
+    );
+
+    values
+}
+
+// [Recent Files]
+// 0=C:\cheat\loader.exe
+pub fn parse_recent_ini(text: &str) -> Vec<String> {
+    let mut files = vec![];
+    let mut recent = false;
+
+    for line in text.lines().map(|line| line.trim()) {
+        if line.starts_with('[') && line.ends_with(']') {
+            recent = line.to_lowercase().contains("recent");
+            continue;
+        }
+
+        if !recent {
+            continue;
+        }
+
+        if let Some((_, value)) = line.split_once('=') {
+            if !value.trim().is_empty() {
+                files.push(value.trim().to_string());
+            }
+        }
+    }
+
+    files
+}
+
+fn modified_time(path: &Path) -> i64 {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_cheat_table() {
+        let table = r#"<?xml version="1.0" encoding="utf-8"?>
+<CheatTable CheatEngineTableVersion="45">
+  <CheatEntries>
+    <CheatEntry>
+      <Description>"health"</Description>
+      <Address>"client.dll"+1A2B3C</Address>
+    </CheatEntry>
+    <CheatEntry>
+      <Address>"CS2.exe"+1234</Address>
+    </CheatEntry>
+  </CheatEntries>
+  <LuaScript>openProcess("cs2.exe")</LuaScript>
+</CheatTable>"#;
+
+        assert_eq!(parse_cheat_table(table), ["cs2.exe"]);
+        assert!(parse_cheat_table("<CheatTable/>").is_empty());
+    }
+
+    #[test]
+    fn parses_recent_ini() {
+        let ini = "[Engine]\r\n\
+                   CalculationType=0\r\n\
+                   [Recent Files]\r\n\
+                   0=C:\\cheat\\loader.exe\r\n\
+                   1=\r\n\
+                   2= D:\\games\\cs2.exe \r\n\
+                   [Gui]\r\n\
+                   Font=C:\\Windows\\Fonts\\font.ttf\r\n";
+
+        assert_eq!(
+            parse_recent_ini(ini),
+            ["C:\\cheat\\loader.exe", "D:\\games\\cs2.exe"]
+        );
+    }
+
+    #[test]
+    fn classifies_files() {
+        assert!(matches!(
+            classify_file("table.ct"),
+            Some(FileKind::CheatTable)
+        ));
+        assert!(matches!(
+            classify_file("x32dbg.ini"),
+            Some(FileKind::DebuggerConfig)
+        ));
+        assert!(matches!(
+            classify_file("dbk64.sys"),
+            Some(FileKind::Other(CHEAT_ENGINE, _))
+        ));
+        assert!(matches!(
+            classify_file("cheatengine-x86_64.exe"),
+            Some(FileKind::Other(CHEAT_ENGINE, _))
+        ));
+        assert!(matches!(
+            classify_file("project.rcnet"),
+            Some(FileKind::Other(RECLASS, _))
+        ));
+        assert!(classify_file("x64dbg.zip").is_none());
+        assert!(classify_file("notes.txt").is_none());
+    }
+}
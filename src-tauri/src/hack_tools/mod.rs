@@ -0,0 +1 @@
+pub mod hack_tools;
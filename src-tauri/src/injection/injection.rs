@@ -0,0 +1,271 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use windows::Win32::System::Registry::HKEY_LOCAL_MACHINE;
+use windows_registry::{Key, Type, LOCAL_MACHINE};
+
+use crate::utils::{expand_environment_strings, get_key_last_write_time, get_windows_dir};
+
+const IFEO_KEYS: [&str; 2] = [
+    "SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion\\Image File Execution Options",
+    "SOFTWARE\\WOW6432Node\\Microsoft\\Windows NT\\CurrentVersion\\Image File Execution Options",
+];
+const IFEO_TARGETS: [&str; 2] = ["cs2.exe", "steam.exe"];
+const IFEO_VALUES: [&str; 3] = ["Debugger", "GlobalFlag", "VerifierDlls"];
+
+const APPINIT_KEYS: [&str; 2] = [
+    "SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion\\Windows",
+    "SOFTWARE\\WOW6432Node\\Microsoft\\Windows NT\\CurrentVersion\\Windows",
+];
+const APPINIT_VALUES: [&str; 3] = [
+    "AppInit_DLLs",
+    "LoadAppInit_DLLs",
+    "RequireSignedAppInit_DLLs",
+];
+
+const APP_CERT_DLLS_KEY: &str = "SYSTEM\\CurrentControlSet\\Control\\Session Manager\\AppCertDlls";
+const KNOWN_DLLS_KEY: &str = "SYSTEM\\CurrentControlSet\\Control\\Session Manager\\KnownDLLs";
+
+// стандартный набор KnownDLLs windows 10 и 11, включая библиотеки wow64 и arm64
+const DEFAULT_KNOWN_DLLS: [&str; 40] = [
+    "_wow64cpu",
+    "_wowarmhw",
+    "_xtajit",
+    "advapi32",
+    "clbcatq",
+    "combase",
+    "comdlg32",
+    "coml2",
+    "difxapi",
+    "gdi32",
+    "gdiplus",
+    "imagehlp",
+    "imm32",
+    "kernel32",
+    "msctf",
+    "msvcrt",
+    "normaliz",
+    "nsi",
+    "ole32",
+    "oleaut32",
+    "psapi",
+    "rpcrt4",
+    "sechost",
+    "setupapi",
+    "shcore",
+    "shell32",
+    "shlwapi",
+    "user32",
+    "wldap32",
+    "wow64",
+    "wow64base",
+    "wow64con",
+    "wow64cpu",
+    "wow64win",
+    "wowarmhw",
+    "ws2_32",
+    "xtajit",
+    "xtajit64",
+    "dlldirectory",
+    "dlldirectory32",
+];
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct InjectionEntry {
+    pub source: String,
+    pub name: String,
+    pub value: String,
+    pub last_write: i64,
+    pub reason: Option<&'static str>,
+}
+
+pub fn collect_injection_vectors() -> Vec<InjectionEntry> {
+    let mut entries = vec![];
+
+    entries.extend(read_ifeo());
+    entries.extend(read_appinit());
+    entries.extend(read_app_cert_dlls());
+    entries.extend(read_known_dlls());
+
+    entries
+}
+
+fn read_ifeo() -> Vec<InjectionEntry> {
+    let mut entries = vec![];
+
+    for path in IFEO_KEYS {
+        let Ok(ifeo) = LOCAL_MACHINE.open(path) else {
+            continue;
+        };
+
+        let Ok(images) = ifeo.keys() else {
+            continue;
+        };
+
+        for image in images {
+            let Ok(key) = ifeo.open(&image) else {
+                continue;
+            };
+
+            let is_target = IFEO_TARGETS.iter().any(|t| t.eq_ignore_ascii_case(&image));
+            let source = format!("{}\\{}", path, image);
+
+            for name in IFEO_VALUES {
+                // отладчик подменяет запуск любого образа, остальное проверяем только у игры и steam
+                if !is_target && name.ne("Debugger") {
+                    continue;
+                }
+
+                let Some(value) = read_value(&key, name) else {
+                    continue;
+                };
+
+                entries.push(InjectionEntry {
+                    last_write: get_key_last_write_time(HKEY_LOCAL_MACHINE, &source),
+                    source: source.clone(),
+                    name: name.to_string(),
+                    reason: Some(match name {
+                        "Debugger" => "вместо программы запускается отладчик",
+                        "GlobalFlag" => "изменены флаги загрузчика",
+                        _ => "загружаются библиотеки верификатора",
+                    }),
+                    value,
+                });
+            }
+        }
+    }
+
+    entries
+}
+
+fn read_appinit() -> Vec<InjectionEntry> {
+    let mut entries = vec![];
+
+    for path in APPINIT_KEYS {
+        let Ok(key) = LOCAL_MACHINE.open(path) else {
+            continue;
+        };
+
+        let last_write = get_key_last_write_time(HKEY_LOCAL_MACHINE, path);
+        let dlls = key.get_string("AppInit_DLLs").unwrap_or_default();
+        let enabled = key.get_u32("LoadAppInit_DLLs").unwrap_or(0) != 0;
+
+        for name in APPINIT_VALUES {
+            let Some(value) = read_value(&key, name) else {
+                continue;
+            };
+
+            entries.push(InjectionEntry {
+                source: path.to_string(),
+                name: name.to_string(),
+                reason: match name {
+                    "AppInit_DLLs" if !value.trim().is_empty() && enabled => {
+                        Some("библиотеки загружаются в каждый процесс с user32")
+                    }
+                    "AppInit_DLLs" if !value.trim().is_empty() => Some("список библиотек не пуст"),
+                    "LoadAppInit_DLLs" if enabled && !dlls.trim().is_empty() => {
+                        Some("загрузка AppInit_DLLs включена")
+                    }
+                    "RequireSignedAppInit_DLLs" if value.eq("0") => {
+                        Some("разрешены неподписанные библиотеки")
+                    }
+                    _ => None,
+                },
+                value,
+                last_write,
+            });
+        }
+    }
+
+    entries
+}
+
+// любая библиотека в AppCertDlls загружается в процессы, вызывающие CreateProcess
+fn read_app_cert_dlls() -> Vec<InjectionEntry> {
+    let Ok(key) = LOCAL_MACHINE.open(APP_CERT_DLLS_KEY) else {
+        return vec![];
+    };
+
+    let Ok(values) = key.values() else {
+        return vec![];
+    };
+
+    let last_write = get_key_last_write_time(HKEY_LOCAL_MACHINE, APP_CERT_DLLS_KEY);
+
+    values
+        .filter_map(|(name, _)| {
+            Some(InjectionEntry {
+                source: APP_CERT_DLLS_KEY.to_string(),
+                value: read_value(&key, &name)?,
+                name,
+                last_write,
+                reason: Some("библиотека загружается при создании процессов"),
+            })
+        })
+        .collect()
+}
+
+fn read_known_dlls() -> Vec<InjectionEntry> {
+    let Ok(key) = LOCAL_MACHINE.open(KNOWN_DLLS_KEY) else {
+        return vec![];
+    };
+
+    let Ok(values) = key.values() else {
+        return vec![];
+    };
+
+    let last_write = get_key_last_write_time(HKEY_LOCAL_MACHINE, KNOWN_DLLS_KEY);
+    let system_dir = format!("{}\\System32", get_windows_dir());
+
+    values
+        .filter_map(|(name, _)| {
+            let value = read_value(&key, &name)?;
+            let lower = name.to_lowercase();
+
+            let reason = if !DEFAULT_KNOWN_DLLS.contains(&lower.as_str()) {
+                Some("нестандартная запись")
+            } else if lower.starts_with("dlldirectory") {
+                let expected = match lower.as_str() {
+                    "dlldirectory" => "\\system32",
+                    _ => "\\syswow64",
+                };
+
+                (!expand_environment_strings(&value)
+                    .to_lowercase()
+                    .trim_end_matches('\\')
+                    .ends_with(expected))
+                .then_some("изменен каталог KnownDLLs")
+            } else {
+                // записи с подчеркиванием указывают на ту же библиотеку без него
+                let stem = value.to_lowercase();
+                (!stem
+                    .trim_end_matches(".dll")
+                    .eq(lower.trim_start_matches('_'))
+                    || !Path::new(&system_dir).join(&value).is_file())
+                .then_some("имя не совпадает с файлом в System32")
+            };
+
+            Some(InjectionEntry {
+                source: KNOWN_DLLS_KEY.to_string(),
+                name,
+                value,
+                last_write,
+                reason,
+            })
+        })
+        // неизмененные записи не выводим, их около сорока на любой системе
+        .filter(|entry| entry.reason.is_some())
+        .collect()
+}
+
+fn read_value(key: &Key, name: &str) -> Option<String> {
+    let value = key.get_value(name).ok()?;
+
+    match value.ty() {
+        Type::U32 => key.get_u32(name).ok().map(|v| v.to_string()),
+        Type::U64 => key.get_u64(name).ok().map(|v| v.to_string()),
+        Type::String | Type::ExpandString => key.get_string(name).ok(),
+        Type::MultiString => key.get_multi_string(name).ok().map(|v| v.join(", ")),
+        _ => None,
+    }
+}
@@ -4,7 +4,8 @@ use std::{
 };
 
 use analyzer::{
-    create_analyzer_context, create_analyzer_context_from_url, generate_context, save_context,
+    create_analyzer_context, create_analyzer_context_from_url, generate_context,
+    get_quarantine_context, get_recycle_bin_context, save_context, scan_vulnerable_drivers,
 };
 use browser::{
     get_browser_cache_data, get_browser_download_data, get_browser_visit_data,
@@ -29,17 +30,36 @@ use vmdetect::is_vm;
 
 pub mod restore;
 pub mod analyzer;
+pub mod archives;
+pub mod autostart;
+pub mod bcd;
 pub mod browser;
+pub mod defender;
 pub mod device_id;
+pub mod drivers;
 pub mod emitter;
+pub mod ese;
+pub mod evtx;
+pub mod hack_tools;
+pub mod injection;
 pub mod mini_dat;
+pub mod network;
+pub mod notifications;
+pub mod powershell;
 pub mod process;
+pub mod profiles;
+pub mod recycle_bin;
+pub mod regf;
+pub mod remote_access;
+pub mod scheduled_tasks;
 pub mod shellbag;
 pub mod steam;
 pub mod storage;
+pub mod timeline;
 pub mod usn_journal;
 pub mod utils;
 pub mod vmdetect;
+pub mod wer;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
@@ -97,6 +117,9 @@ pub fn run() {
             create_analyzer_context,
             create_analyzer_context_from_url,
             generate_context,
+            scan_vulnerable_drivers,
+            get_quarantine_context,
+            get_recycle_bin_context,
             run_main_window_and_close_preload,
             get_storage,
             set_storage,
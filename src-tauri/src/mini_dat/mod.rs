@@ -1,13 +1,19 @@
 use mini_dat::{MiniDat, MiniDatEmployee, MiniDatInfo};
 use registry_md::{
-    AppCompatCache, AppSwitched, Bam, Radar, SevenZip, ShellBag, UserAssist, WinRar, SRUM,
+    AppCompatCache, AppSwitched, Autostart, Bam, Bandizip, BootConfig, CompatibilityAssistant,
+    CompatibilityLayers, DefenderDetections, DefenderExclusions, DefenderQuarantine,
+    DefenderSettings, Drivers, EventLog, FirewallRules, HackTools, Hosts, InjectionVectors,
+    MuiCache, Notifications, Pca, PeaZip, PowerShellHistory, Radar, RecycleBin, RemoteSessions,
+    ScheduledTasks, SevenZip, ShellBag, Timeline, UserAssist, VulnerableDrivers, WerReports,
+    WinRar, WinZip, SRUM,
 };
 
 pub mod mini_dat;
+pub mod pca;
 pub mod registry_md;
 pub mod srum;
 
-pub const MINI_DAT_META: [MiniDatInfo; 9] = [
+pub const MINI_DAT_META: [MiniDatInfo; 38] = [
     MiniDatInfo {
         id: "radar",
         name: "Отсканированные файлы",
@@ -25,14 +31,35 @@ pub const MINI_DAT_META: [MiniDatInfo; 9] = [
     MiniDatInfo {
         id: "seven_zip",
         name: "Использование архивов 7Zip",
-        description: "Произведенные когда либо любые действия с архивами через 7Zip File Manager.",
+        description: "Произведенные когда либо любые действия с архивами через 7Zip File Manager: открытые и созданные архивы, папки распаковки, история папок и копирования.",
         filtering: true,
         stable: true
     },
     MiniDatInfo {
         id: "winrar",
         name: "Использование архивов WinRar",
-        description: "Произведенные когда либо любые действия с архивом .rar (в основном) через WinRar Archive.",
+        description: "Произведенные когда либо любые действия с архивом .rar (в основном) через WinRar Archive, а также имена архивов и папки распаковки из диалогов.",
+        filtering: true,
+        stable: true
+    },
+    MiniDatInfo {
+        id: "bandizip",
+        name: "Использование архивов Bandizip",
+        description: "Недавно открытые в Bandizip архивы и папки, в которые они распаковывались.",
+        filtering: true,
+        stable: true
+    },
+    MiniDatInfo {
+        id: "peazip",
+        name: "Использование архивов PeaZip",
+        description: "История архивов и папок распаковки из настроек установленного PeaZip (conf.txt).",
+        filtering: true,
+        stable: true
+    },
+    MiniDatInfo {
+        id: "winzip",
+        name: "Использование архивов WinZip",
+        description: "Недавно открытые в WinZip архивы и папки, в которые они распаковывались.",
         filtering: true,
         stable: true
     },
@@ -50,6 +77,13 @@ pub const MINI_DAT_META: [MiniDatInfo; 9] = [
         filtering: true,
         stable: true
     },
+    MiniDatInfo {
+        id: "pca",
+        name: "Помощник по совместимости программ (PCA)",
+        description: "Windows 11 22H2+ записывает каждую программу, запущенную из проводника, в PcaAppLaunchDic.txt и PcaGeneralDb. Записи содержат полный путь и время запуска в UTC и не удаляются очистителями реестра.",
+        filtering: true,
+        stable: true
+    },
     MiniDatInfo {
         id: "app_switched",
         name: "Статистика переключения между приложениями",
@@ -57,6 +91,27 @@ pub const MINI_DAT_META: [MiniDatInfo; 9] = [
         filtering: true,
         stable: true
     },
+    MiniDatInfo {
+        id: "mui_cache",
+        name: "Кэш MUI",
+        description: "Хранятся отображаемые имена (FriendlyAppName) исполняемых файлов, которые запускались пользователем.",
+        filtering: true,
+        stable: true
+    },
+    MiniDatInfo {
+        id: "compatibility_assistant",
+        name: "Помощник по совместимости (Store)",
+        description: "Хранится список исполняемых файлов, запуск которых отследил помощник по совместимости программ.",
+        filtering: true,
+        stable: true
+    },
+    MiniDatInfo {
+        id: "compatibility_layers",
+        name: "Режимы совместимости",
+        description: "Флаги совместимости, выставленные исполняемым файлам. Лоадеры часто настраивают на постоянный запуск от имени администратора, что само по себе является уликой.",
+        filtering: true,
+        stable: true
+    },
     MiniDatInfo {
         id: "shellbag",
         name: "Папки, с которыми было взаимодействие",
@@ -67,7 +122,161 @@ pub const MINI_DAT_META: [MiniDatInfo; 9] = [
     MiniDatInfo {
         id: "srum",
         name: "Монитор использования системных ресурсов",
-        description: "Исполняемые файлы, которые запускались в данной системе.",
+        description: "Приложения, службы и пакеты Windows, которые запускались в данной системе, с указанием пользователей, от имени которых они работали.",
+        filtering: true,
+        stable: true
+    },
+    MiniDatInfo {
+        id: "srum_app_usage",
+        name: "Использование ресурсов приложениями",
+        description: "Почасовая статистика SRUM по каждому приложению: пользователь, время процессора на переднем плане, объем прочитанных и записанных данных.",
+        filtering: true,
+        stable: true
+    },
+    MiniDatInfo {
+        id: "srum_network_usage",
+        name: "Использование сети приложениями",
+        description: "Почасовая статистика SRUM по сетевому трафику каждого приложения: пользователь, отправленные и полученные байты.",
+        filtering: true,
+        stable: true
+    },
+    MiniDatInfo {
+        id: "event_log",
+        name: "Журналы событий Windows",
+        description: "Создание процессов, очистка журналов, изменение системного времени, установка служб, обнаружения Защитника Windows и подключение устройств.",
+        filtering: true,
+        stable: true
+    },
+    MiniDatInfo {
+        id: "defender_detections",
+        name: "Обнаружения Защитника Windows",
+        description: "История обнаружений и журналы MPLog Защитника Windows: имя угрозы, путь к файлу и предпринятое действие, в том числе разрешенные пользователем угрозы.",
+        filtering: true,
+        stable: true
+    },
+    MiniDatInfo {
+        id: "defender_quarantine",
+        name: "Карантин Защитника Windows",
+        description: "Файлы, помещенные Защитником Windows в карантин: исходный путь, имя угрозы и хэши содержимого, по которым можно определить сборку чита даже после удаления файла с диска.",
+        filtering: true,
+        stable: true
+    },
+    MiniDatInfo {
+        id: "defender_exclusions",
+        name: "Исключения Защитника Windows",
+        description: "Пути, процессы и расширения, исключенные из проверки Защитника Windows, со временем изменения ключа. Исключения пользовательских папок отмечены отдельно.",
+        filtering: true,
+        stable: true
+    },
+    MiniDatInfo {
+        id: "defender_settings",
+        name: "Состояние Защитника Windows",
+        description: "Защита в реальном времени, защита от изменений и отключение антивируса через реестр или групповые политики, со временем изменения ключа.",
+        filtering: true,
+        stable: true
+    },
+    MiniDatInfo {
+        id: "scheduled_tasks",
+        name: "Запланированные задачи",
+        description: "Задачи планировщика: запускаемые программы с аргументами, триггеры, автор и дата регистрации. Отмечены скрытые задачи и задачи, у которых xml файл и запись в реестре не совпадают.",
+        filtering: true,
+        stable: true
+    },
+    MiniDatInfo {
+        id: "autostart",
+        name: "Автозагрузка",
+        description: "Программы, запускаемые автоматически: ключи Run и RunOnce, Winlogon, папки автозагрузки и службы. Для каждой записи указан источник и хэш файла, если он существует.",
+        filtering: true,
+        stable: true
+    },
+    MiniDatInfo {
+        id: "drivers",
+        name: "Драйверы ядра",
+        description: "Службы драйверов из всех наборов ControlSet: путь, тип запуска, группа и время изменения ключа. Отмечены драйверы без файла на диске, расположенные вне System32\\drivers или оставшиеся только в резервном наборе.",
+        filtering: true,
+        stable: true
+    },
+    MiniDatInfo {
+        id: "vulnerable_drivers",
+        name: "Уязвимые драйверы",
+        description: "Файлы .sys из System32\\drivers, совпавшие со списком уязвимых драйверов, через которые загружаются читы уровня ядра. Временные папки и профили пользователей проверяются отдельной задачей.",
+        filtering: true,
+        stable: true
+    },
+    MiniDatInfo {
+        id: "boot_config",
+        name: "Конфигурация загрузки",
+        description: "Записи загрузки Windows из хранилища BCD: тестовая подпись, отключение проверки целостности, отладка и запуск гипервизора. Дополнительные записи ОС могут указывать на отдельную систему для игры с читами.",
+        filtering: true,
+        stable: true
+    },
+    MiniDatInfo {
+        id: "injection_vectors",
+        name: "Внедрение библиотек",
+        description: "Настройки, позволяющие загрузить библиотеку в игру без отдельного инжектора: Image File Execution Options для cs2.exe и steam.exe, AppInit_DLLs, AppCertDlls и изменения KnownDLLs.",
+        filtering: true,
+        stable: true
+    },
+    MiniDatInfo {
+        id: "hosts",
+        name: "Файл hosts",
+        description: "Нестандартные записи файла hosts. Отмечены записи, перенаправляющие серверы Valve, FACEIT и античитов.",
+        filtering: true,
+        stable: true
+    },
+    MiniDatInfo {
+        id: "firewall_rules",
+        name: "Правила брандмауэра",
+        description: "Добавленные пользователем и программами правила брандмауэра Windows: приложение, направление, действие и удаленные адреса. Отмечены правила для cs2.exe, steam.exe и клиентов античитов.",
+        filtering: true,
+        stable: true
+    },
+    MiniDatInfo {
+        id: "wer_reports",
+        name: "Отчеты об ошибках (WER)",
+        description: "Отчеты Windows Error Reporting о падениях cs2.exe и steam.exe: время, модуль с ошибкой, код исключения и загруженные модули вне системных папок и папки игры.",
+        filtering: true,
+        stable: true
+    },
+    MiniDatInfo {
+        id: "recycle_bin",
+        name: "Корзина",
+        description: "Файлы в корзине всех пользователей: исходный путь, размер и время удаления. Если содержимое ($R) еще на месте, оно добавляется в контекст анализатора.",
+        filtering: true,
+        stable: true
+    },
+    MiniDatInfo {
+        id: "timeline",
+        name: "Временная шкала Windows",
+        description: "Записи ActivitiesCache.db: приложения и открытые в них документы, время начала и окончания активности и время в фокусе.",
+        filtering: true,
+        stable: true
+    },
+    MiniDatInfo {
+        id: "powershell_history",
+        name: "История PowerShell",
+        description: "Команды из истории PSReadLine всех пользователей. Отмечены загрузки из сети, изменение настроек Защитника, операции с журналом USN и очистка журналов событий.",
+        filtering: true,
+        stable: true
+    },
+    MiniDatInfo {
+        id: "notifications",
+        name: "Уведомления Windows",
+        description: "Всплывающие уведомления Discord, Telegram, браузеров и других приложений из wpndatabase.db: приложение, текст и время получения.",
+        filtering: true,
+        stable: true
+    },
+    MiniDatInfo {
+        id: "remote_sessions",
+        name: "Удаленный доступ",
+        description: "Входящие сессии AnyDesk, TeamViewer и RustDesk из их журналов: id и имя подключавшегося, время начала, длительность и итог подключения.",
+        filtering: true,
+        stable: true
+    },
+    MiniDatInfo {
+        id: "hack_tools",
+        name: "Инструменты для взлома игр",
+        description: "Следы Cheat Engine, x64dbg, ReClass, Process Hacker и System Informer: настройки, служба драйвера dbk64.sys, недавние файлы и таблицы .CT с именами целевых процессов.",
         filtering: true,
         stable: true
     }
@@ -79,13 +288,40 @@ pub fn collect_mini_dat() -> Vec<MiniDat> {
 
     employees.extend(WinRar::run());
     employees.extend(SevenZip::run());
+    employees.extend(Bandizip::run());
+    employees.extend(PeaZip::run());
+    employees.extend(WinZip::run());
     employees.extend(UserAssist::run());
     employees.extend(Radar::run());
     employees.extend(AppCompatCache::run());
     employees.extend(Bam::run());
+    employees.extend(Pca::run());
     employees.extend(AppSwitched::run());
+    employees.extend(MuiCache::run());
+    employees.extend(CompatibilityAssistant::run());
+    employees.extend(CompatibilityLayers::run());
     employees.extend(ShellBag::run());
     employees.extend(SRUM::run());
+    employees.extend(EventLog::run());
+    employees.extend(DefenderDetections::run());
+    employees.extend(DefenderQuarantine::run());
+    employees.extend(DefenderExclusions::run());
+    employees.extend(DefenderSettings::run());
+    employees.extend(ScheduledTasks::run());
+    employees.extend(Autostart::run());
+    employees.extend(Drivers::run());
+    employees.extend(VulnerableDrivers::run());
+    employees.extend(BootConfig::run());
+    employees.extend(InjectionVectors::run());
+    employees.extend(Hosts::run());
+    employees.extend(FirewallRules::run());
+    employees.extend(WerReports::run());
+    employees.extend(RecycleBin::run());
+    employees.extend(Timeline::run());
+    employees.extend(PowerShellHistory::run());
+    employees.extend(Notifications::run());
+    employees.extend(RemoteSessions::run());
+    employees.extend(HackTools::run());
 
     employees
 }
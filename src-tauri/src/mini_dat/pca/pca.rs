@@ -0,0 +1,139 @@
+use std::{cmp::Reverse, fs};
+
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+use crate::utils::{expand_environment_strings, get_windows_dir, text_bytes_to_string};
+
+const PCA_DIR: &str = "appcompat\\pca";
+
+const APP_LAUNCH_DIC: &str = "PcaAppLaunchDic.txt";
+const GENERAL_DBS: [&str; 2] = ["PcaGeneralDb0.txt", "PcaGeneralDb1.txt"];
+
+// время в файлах pca записано в utc
+const PCA_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.f";
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct PcaEntry {
+    pub source: String,
+    pub timestamp: i64,
+    pub path: String,
+    pub product: String,
+    pub company: String,
+    pub version: String,
+    pub exit_message: String,
+}
+
+pub fn collect_pca_entries() -> Vec<PcaEntry> {
+    let dir = format!("{}\\{}", get_windows_dir(), PCA_DIR);
+    let mut entries = vec![];
+
+    if let Some(text) = read_pca_file(&format!("{}\\{}", dir, APP_LAUNCH_DIC)) {
+        entries.extend(parse_app_launch_dic(&text));
+    }
+
+    for name in GENERAL_DBS {
+        if let Some(text) = read_pca_file(&format!("{}\\{}", dir, name)) {
+            entries.extend(parse_general_db(&text, name));
+        }
+    }
+
+    entries.sort_by_key(|entry| Reverse(entry.timestamp));
+    entries
+}
+
+fn read_pca_file(path: &str) -> Option<String> {
+    match fs::read(path) {
+        Ok(data) => Some(text_bytes_to_string(&data)),
+        Err(e) => {
+            if cfg!(dev) {
+                println!("{e:?}");
+            }
+
+            None
+        }
+    }
+}
+
+// C:\Games\cheat.exe|2023-01-01 12:00:00.000
+pub fn parse_app_launch_dic(text: &str) -> Vec<PcaEntry> {
+    text.lines()
+        .filter_map(|line| {
+            let (path, time) = line.trim().rsplit_once('|')?;
+
+            Some(PcaEntry {
+                source: APP_LAUNCH_DIC.to_string(),
+                timestamp: parse_pca_time(time)?,
+                path: path.to_string(),
+                ..Default::default()
+            })
+        })
+        .collect()
+}
+
+// статус|время|тип записи|путь|продукт|компания|версия|id программы|сообщение о завершении
+pub fn parse_general_db(text: &str, source: &str) -> Vec<PcaEntry> {
+    text.lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.trim().split('|').collect();
+
+            if fields.len() < 4 {
+                return None;
+            }
+
+            let field = |index: usize| fields.get(index).unwrap_or(&"").trim().to_string();
+
+            Some(PcaEntry {
+                source: source.to_string(),
+                timestamp: parse_pca_time(fields[1])?,
+                path: expand_environment_strings(fields[3]),
+                product: field(4),
+                company: field(5),
+                version: field(6),
+                exit_message: field(8),
+            })
+        })
+        .collect()
+}
+
+fn parse_pca_time(time: &str) -> Option<i64> {
+    NaiveDateTime::parse_from_str(time.trim(), PCA_TIME_FORMAT)
+        .ok()
+        .map(|time| time.and_utc().timestamp())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_app_launch_dic() {
+        let entries =
+            parse_app_launch_dic("C:\\Games\\cheat.exe|2023-01-01 12:00:00.000\r\nbroken line\r\n");
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].path, "C:\\Games\\cheat.exe");
+        assert_eq!(entries[0].source, APP_LAUNCH_DIC);
+        assert_eq!(entries[0].timestamp, 1672574400);
+    }
+
+    #[test]
+    fn parses_general_db() {
+        let entries = parse_general_db(
+            "0|2023-01-01 12:00:00.123|2|C:\\Games\\loader.exe|Loader|Vendor|1.0.0|0006abc|Abnormal process exit with code 0xc0000005\n\
+             0|not a time|2|C:\\Games\\other.exe\n",
+            GENERAL_DBS[0],
+        );
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].timestamp, 1672574400);
+        assert_eq!(entries[0].path, "C:\\Games\\loader.exe");
+        assert_eq!(entries[0].product, "Loader");
+        assert_eq!(entries[0].company, "Vendor");
+        assert_eq!(entries[0].version, "1.0.0");
+        assert_eq!(
+            entries[0].exit_message,
+            "Abnormal process exit with code 0xc0000005"
+        );
+    }
+}
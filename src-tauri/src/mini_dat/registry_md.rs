@@ -3,24 +3,57 @@ use std::{
     ffi::OsString,
     io::{Bytes, Read},
     os::windows::ffi::OsStringExt,
-    thread,
-    time::Duration,
 };
 use windows::Win32::Storage::FileSystem::QueryDosDeviceW;
-use windows_registry::{Type, CURRENT_USER, LOCAL_MACHINE};
+use windows_registry::{Type, LOCAL_MACHINE};
 
 use crate::{
+    analyzer::analyzer::{system_driver_dirs, Analyzer},
+    archives::history::collect_archive_history,
+    autostart::autostart::collect_autostart,
+    bcd::bcd::collect_boot_entries,
+    defender::{
+        defender::collect_detections,
+        quarantine::collect_quarantine,
+        settings::{collect_exclusions, collect_settings},
+    },
+    drivers::{
+        drivers::{collect_drivers, current_control_set},
+        vulnerable::{load_vulnerable_drivers, DriverMatch},
+    },
+    evtx::events::collect_events,
+    hack_tools::hack_tools::collect_hack_tool_traces,
+    injection::injection::collect_injection_vectors,
+    network::{firewall::collect_firewall_rules, hosts::collect_hosts},
+    notifications::notifications::collect_notifications,
+    powershell::history::collect_powershell_history,
+    profiles::{
+        profiles::{collect_user_profiles, profile_name_by_sid},
+        user_hive::collect_user_hives,
+    },
+    recycle_bin::recycle_bin::collect_recycle_bin,
+    remote_access::remote_access::collect_remote_sessions,
+    scheduled_tasks::scheduled_tasks::collect_scheduled_tasks,
     shellbag::shellbag::collect_shell_bag,
-    utils::{get_current_username_in_sid, known_folder_in_path, rot13, string_to_pcwstr},
+    timeline::timeline::{activity_type_name, collect_timeline},
+    utils::{filetime_to_string, known_folder_in_path, rot13, string_to_pcwstr, unix_to_string},
+    wer::wer::collect_wer_reports,
 };
 
 use super::{
     mini_dat::{MiniDat, MiniDatEmployee, MiniDatWrapper},
-    srum::provider::try_read_srum,
+    pca::pca::collect_pca_entries,
+    srum::provider::{Srum, SRUM_PATH},
 };
 
+const COMPATIBILITY_LAYERS_KEY: &str =
+    "SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion\\AppCompatFlags\\Layers";
+
 pub struct SevenZip {}
 pub struct WinRar {}
+pub struct Bandizip {}
+pub struct PeaZip {}
+pub struct WinZip {}
 pub struct UserAssist {}
 pub struct Radar {}
 pub struct AppCompatCache {}
@@ -28,6 +61,32 @@ pub struct Bam {}
 pub struct AppSwitched {}
 pub struct ShellBag {}
 pub struct SRUM {}
+pub struct SrumAppUsage {}
+pub struct SrumNetworkUsage {}
+pub struct EventLog {}
+pub struct DefenderDetections {}
+pub struct DefenderQuarantine {}
+pub struct DefenderExclusions {}
+pub struct DefenderSettings {}
+pub struct ScheduledTasks {}
+pub struct Autostart {}
+pub struct Drivers {}
+pub struct VulnerableDrivers {}
+pub struct BootConfig {}
+pub struct InjectionVectors {}
+pub struct Hosts {}
+pub struct FirewallRules {}
+pub struct WerReports {}
+pub struct Pca {}
+pub struct MuiCache {}
+pub struct CompatibilityAssistant {}
+pub struct CompatibilityLayers {}
+pub struct RecycleBin {}
+pub struct Timeline {}
+pub struct PowerShellHistory {}
+pub struct Notifications {}
+pub struct RemoteSessions {}
+pub struct HackTools {}
 
 impl MiniDatWrapper for SRUM {
     fn new_instance(value: String) -> MiniDat {
@@ -38,54 +97,719 @@ impl MiniDatWrapper for SRUM {
     }
 }
 
-#[allow(unused_assignments)]
+impl MiniDatWrapper for SrumAppUsage {
+    fn new_instance(value: String) -> MiniDat {
+        MiniDat {
+            value: value,
+            id: "srum_app_usage",
+        }
+    }
+}
+
+impl MiniDatWrapper for SrumNetworkUsage {
+    fn new_instance(value: String) -> MiniDat {
+        MiniDat {
+            value: value,
+            id: "srum_network_usage",
+        }
+    }
+}
+
+// база читается один раз, поэтому все таблицы srum собираются здесь
 impl MiniDatEmployee<MiniDat> for SRUM {
     fn run() -> Vec<MiniDat> {
-        loop {
-            let mut counter = 1;
-            if counter >= 500 {
-                return vec![];
+        let Some(srum) = Srum::open(SRUM_PATH) else {
+            return vec![];
+        };
+
+        let mut response: Vec<MiniDat> = srum
+            .apps()
+            .par_iter()
+            .map(|app| {
+                SRUM::new_instance(if app.users.is_empty() {
+                    format!("{} | {}", app.value, app.kind.name())
+                } else {
+                    format!(
+                        "{} | {} | {}",
+                        app.value,
+                        app.kind.name(),
+                        app.users.join(", ")
+                    )
+                })
+            })
+            .collect();
+
+        response.extend(srum.app_usage().iter().map(|usage| {
+            SrumAppUsage::new_instance(format!(
+                "{} | {} | {} | циклы cpu: {} | прочитано: {} байт | записано: {} байт",
+                usage.timestamp,
+                usage.app,
+                usage.user,
+                usage.foreground_cycle_time,
+                usage.bytes_read,
+                usage.bytes_written
+            ))
+        }));
+
+        response.extend(srum.network_usage().iter().map(|usage| {
+            SrumNetworkUsage::new_instance(format!(
+                "{} | {} | {} | отправлено: {} байт | получено: {} байт",
+                usage.timestamp, usage.app, usage.user, usage.bytes_sent, usage.bytes_received
+            ))
+        }));
+
+        response
+    }
+}
+
+impl MiniDatWrapper for EventLog {
+    fn new_instance(value: String) -> MiniDat {
+        MiniDat {
+            value: value,
+            id: "event_log",
+        }
+    }
+}
+
+impl MiniDatEmployee<MiniDat> for EventLog {
+    fn run() -> Vec<MiniDat> {
+        collect_events()
+            .par_iter()
+            .map(|event| {
+                EventLog::new_instance(format!(
+                    "{} | {} {} | {} | {}",
+                    filetime_to_string(event.timestamp),
+                    event.log,
+                    event.event_id,
+                    event.description,
+                    event.details
+                ))
+            })
+            .collect()
+    }
+}
+
+impl MiniDatWrapper for DefenderDetections {
+    fn new_instance(value: String) -> MiniDat {
+        MiniDat {
+            value: value,
+            id: "defender_detections",
+        }
+    }
+}
+
+impl MiniDatEmployee<MiniDat> for DefenderDetections {
+    fn run() -> Vec<MiniDat> {
+        collect_detections()
+            .par_iter()
+            .map(|detection| {
+                DefenderDetections::new_instance(format!(
+                    "{} | {} | {} | {} | {}",
+                    unix_to_string(detection.timestamp),
+                    detection.threat,
+                    detection.path,
+                    detection.action,
+                    detection.source
+                ))
+            })
+            .collect()
+    }
+}
+
+impl MiniDatWrapper for DefenderQuarantine {
+    fn new_instance(value: String) -> MiniDat {
+        MiniDat {
+            value: value,
+            id: "defender_quarantine",
+        }
+    }
+}
+
+impl MiniDatEmployee<MiniDat> for DefenderQuarantine {
+    fn run() -> Vec<MiniDat> {
+        collect_quarantine()
+            .par_iter()
+            .map(|item| {
+                DefenderQuarantine::new_instance(format!(
+                    "{} | {} | {} | {} байт | sha1: {} | sha256: {}",
+                    filetime_to_string(item.timestamp),
+                    item.threat,
+                    item.path,
+                    item.size,
+                    item.sha1,
+                    item.sha256
+                ))
+            })
+            .collect()
+    }
+}
+
+impl MiniDatWrapper for DefenderExclusions {
+    fn new_instance(value: String) -> MiniDat {
+        MiniDat {
+            value: value,
+            id: "defender_exclusions",
+        }
+    }
+}
+
+impl MiniDatEmployee<MiniDat> for DefenderExclusions {
+    fn run() -> Vec<MiniDat> {
+        collect_exclusions()
+            .par_iter()
+            .map(|exclusion| {
+                DefenderExclusions::new_instance(format!(
+                    "{} | {} | {} | изменено: {}{}",
+                    exclusion.value,
+                    exclusion.kind,
+                    if exclusion.policy {
+                        "политика"
+                    } else {
+                        "локально"
+                    },
+                    filetime_to_string(exclusion.last_write),
+                    if exclusion.user_writable {
+                        " | пользовательская папка"
+                    } else {
+                        ""
+                    }
+                ))
+            })
+            .collect()
+    }
+}
+
+impl MiniDatWrapper for DefenderSettings {
+    fn new_instance(value: String) -> MiniDat {
+        MiniDat {
+            value: value,
+            id: "defender_settings",
+        }
+    }
+}
+
+impl MiniDatEmployee<MiniDat> for DefenderSettings {
+    fn run() -> Vec<MiniDat> {
+        collect_settings()
+            .par_iter()
+            .map(|setting| {
+                DefenderSettings::new_instance(format!(
+                    "{} = {} | защита {} | {} | изменено: {}",
+                    setting.name,
+                    setting.value,
+                    if setting.protection_enabled {
+                        "включена"
+                    } else {
+                        "отключена"
+                    },
+                    if setting.policy {
+                        "политика"
+                    } else {
+                        "локально"
+                    },
+                    filetime_to_string(setting.last_write)
+                ))
+            })
+            .collect()
+    }
+}
+
+impl MiniDatWrapper for ScheduledTasks {
+    fn new_instance(value: String) -> MiniDat {
+        MiniDat {
+            value: value,
+            id: "scheduled_tasks",
+        }
+    }
+}
+
+impl MiniDatEmployee<MiniDat> for ScheduledTasks {
+    fn run() -> Vec<MiniDat> {
+        collect_scheduled_tasks()
+            .par_iter()
+            .map(|task| {
+                let mut value = match &task.xml {
+                    Some(xml) => format!(
+                        "{} | {} | триггеры: {} | автор: {} | дата: {}",
+                        task.path,
+                        xml.actions.join("; "),
+                        xml.triggers.join(", "),
+                        xml.author,
+                        xml.date
+                    ),
+                    None => format!("{} | {}", task.path, task.registry_actions),
+                };
+
+                if task.xml.as_ref().is_some_and(|xml| xml.hidden) {
+                    value.push_str(" | скрытая");
+                }
+
+                if task.xml.is_none() {
+                    value.push_str(" | нет xml файла");
+                }
+
+                if !task.registry {
+                    value.push_str(" | нет записи в реестре");
+                } else if !task.tree {
+                    value.push_str(" | удалена из TaskCache\\Tree");
+                } else if !task.security_descriptor {
+                    value.push_str(" | удален дескриптор безопасности");
+                }
+
+                ScheduledTasks::new_instance(value)
+            })
+            .collect()
+    }
+}
+
+impl MiniDatWrapper for Autostart {
+    fn new_instance(value: String) -> MiniDat {
+        MiniDat {
+            value: value,
+            id: "autostart",
+        }
+    }
+}
+
+impl MiniDatEmployee<MiniDat> for Autostart {
+    fn run() -> Vec<MiniDat> {
+        collect_autostart()
+            .par_iter()
+            .map(|entry| {
+                Autostart::new_instance(format!(
+                    "{} | {} | {} | {}",
+                    entry.path,
+                    entry.name,
+                    entry.source,
+                    match (&entry.sha256, entry.exists) {
+                        (Some(sha256), _) => format!("sha256: {}", sha256),
+                        (None, true) => String::from("папка"),
+                        (None, false) => String::from("файл не найден"),
+                    }
+                ))
+            })
+            .collect()
+    }
+}
+
+impl MiniDatWrapper for Drivers {
+    fn new_instance(value: String) -> MiniDat {
+        MiniDat {
+            value: value,
+            id: "drivers",
+        }
+    }
+}
+
+impl MiniDatEmployee<MiniDat> for Drivers {
+    fn run() -> Vec<MiniDat> {
+        collect_drivers()
+            .par_iter()
+            .map(|driver| {
+                let mut value = format!(
+                    "{} | {} | запуск: {} | группа: {} | изменено: {} | {}",
+                    driver.name,
+                    driver.path,
+                    driver.start,
+                    driver.group,
+                    filetime_to_string(driver.last_write),
+                    driver.control_sets.join(", ")
+                );
+
+                if !driver.exists {
+                    value.push_str(" | файл не найден");
+                }
+
+                if driver.outside_drivers_dir {
+                    value.push_str(" | вне System32\\drivers");
+                }
+
+                if !driver.in_current {
+                    value.push_str(" | нет в текущем наборе");
+                }
+
+                Drivers::new_instance(value)
+            })
+            .collect()
+    }
+}
+
+impl MiniDatWrapper for VulnerableDrivers {
+    fn new_instance(value: String) -> MiniDat {
+        MiniDat {
+            value: value,
+            id: "vulnerable_drivers",
+        }
+    }
+}
+
+impl MiniDatEmployee<MiniDat> for VulnerableDrivers {
+    fn run() -> Vec<MiniDat> {
+        // временные папки и профили обходятся только отдельной командой scan_vulnerable_drivers
+        wrap_driver_matches(Analyzer::scan_vulnerable_drivers(
+            system_driver_dirs(),
+            &load_vulnerable_drivers(),
+        ))
+    }
+}
+
+pub fn wrap_driver_matches(matches: Vec<DriverMatch>) -> Vec<MiniDat> {
+    matches
+        .par_iter()
+        .map(|item| {
+            VulnerableDrivers::new_instance(format!(
+                "{} | {} | sha256: {} | authentihash: {}{}",
+                item.path,
+                item.driver.name,
+                item.sha256,
+                item.authentihash,
+                if item.name_only {
+                    " | совпадает только имя"
+                } else {
+                    ""
+                }
+            ))
+        })
+        .collect()
+}
+
+impl MiniDatWrapper for BootConfig {
+    fn new_instance(value: String) -> MiniDat {
+        MiniDat {
+            value: value,
+            id: "boot_config",
+        }
+    }
+}
+
+impl MiniDatEmployee<MiniDat> for BootConfig {
+    fn run() -> Vec<MiniDat> {
+        collect_boot_entries()
+            .par_iter()
+            .map(|entry| {
+                let mut flags = vec![];
+
+                if entry.test_signing {
+                    flags.push(String::from("testsigning"));
+                }
+
+                if entry.no_integrity_checks {
+                    flags.push(String::from("nointegritychecks"));
+                }
+
+                if entry.debug {
+                    flags.push(String::from("debug"));
+                }
+
+                if entry.boot_debug {
+                    flags.push(String::from("bootdebug"));
+                }
+
+                if entry.winpe {
+                    flags.push(String::from("winpe"));
+                }
+
+                if entry.hypervisor_launch_type == Some(0) {
+                    flags.push(String::from("hypervisorlaunchtype off"));
+                }
+
+                if let Some(nx_policy) = entry.nx_policy {
+                    flags.push(format!("nx {}", nx_policy));
+                }
+
+                if !entry.load_options.is_empty() {
+                    flags.push(format!("loadoptions {}", entry.load_options));
+                }
+
+                // загрузчики winpe (среда восстановления) есть в любой установке и в меню не выводятся
+                if !entry.is_default && !entry.winpe {
+                    flags.push(String::from("дополнительная ОС"));
+                }
+
+                if !entry.in_display_order && !entry.winpe {
+                    flags.push(String::from("нет в меню загрузки"));
+                }
+
+                BootConfig::new_instance(format!(
+                    "{} | {} | {}{} | {} | {}",
+                    entry.description,
+                    entry.id,
+                    entry.system_root,
+                    entry.path,
+                    flags.join(", "),
+                    entry.source
+                ))
+            })
+            .collect()
+    }
+}
+
+impl MiniDatWrapper for InjectionVectors {
+    fn new_instance(value: String) -> MiniDat {
+        MiniDat {
+            value: value,
+            id: "injection_vectors",
+        }
+    }
+}
+
+impl MiniDatEmployee<MiniDat> for InjectionVectors {
+    fn run() -> Vec<MiniDat> {
+        collect_injection_vectors()
+            .par_iter()
+            .map(|entry| {
+                InjectionVectors::new_instance(format!(
+                    "{} = {} | {} | изменено: {}{}",
+                    entry.name,
+                    entry.value,
+                    entry.source,
+                    filetime_to_string(entry.last_write),
+                    entry
+                        .reason
+                        .map(|reason| format!(" | {}", reason))
+                        .unwrap_or_default()
+                ))
+            })
+            .collect()
+    }
+}
+
+impl MiniDatWrapper for Hosts {
+    fn new_instance(value: String) -> MiniDat {
+        MiniDat {
+            value: value,
+            id: "hosts",
+        }
+    }
+}
+
+impl MiniDatEmployee<MiniDat> for Hosts {
+    fn run() -> Vec<MiniDat> {
+        collect_hosts()
+            .par_iter()
+            .map(|entry| {
+                Hosts::new_instance(format!(
+                    "{} -> {}{}",
+                    entry.host,
+                    entry.address,
+                    if entry.watched {
+                        " | сервер игры или античита"
+                    } else {
+                        ""
+                    }
+                ))
+            })
+            .collect()
+    }
+}
+
+impl MiniDatWrapper for FirewallRules {
+    fn new_instance(value: String) -> MiniDat {
+        MiniDat {
+            value: value,
+            id: "firewall_rules",
+        }
+    }
+}
+
+impl MiniDatEmployee<MiniDat> for FirewallRules {
+    fn run() -> Vec<MiniDat> {
+        collect_firewall_rules()
+            .par_iter()
+            .map(|rule| {
+                FirewallRules::new_instance(format!(
+                    "{} | {} {} | {} | адреса: {} | порты: {} | {}{}{}",
+                    rule.name,
+                    rule.action,
+                    rule.direction,
+                    if rule.app.is_empty() {
+                        &rule.service
+                    } else {
+                        &rule.app
+                    },
+                    rule.remote_addresses.join(", "),
+                    rule.remote_ports.join(", "),
+                    if rule.active {
+                        "активно"
+                    } else {
+                        "отключено"
+                    },
+                    if rule.policy {
+                        " | политика"
+                    } else {
+                        ""
+                    },
+                    if rule.watched {
+                        " | игра или античит"
+                    } else {
+                        ""
+                    }
+                ))
+            })
+            .collect()
+    }
+}
+
+impl MiniDatWrapper for WerReports {
+    fn new_instance(value: String) -> MiniDat {
+        MiniDat {
+            value: value,
+            id: "wer_reports",
+        }
+    }
+}
+
+impl MiniDatEmployee<MiniDat> for WerReports {
+    fn run() -> Vec<MiniDat> {
+        collect_wer_reports()
+            .par_iter()
+            .map(|report| {
+                WerReports::new_instance(format!(
+                    "{} | {} | {} | {} | модуль: {} | код: {} | неизвестные модули: {} | {}",
+                    if report.user.is_empty() {
+                        "система"
+                    } else {
+                        &report.user
+                    },
+                    filetime_to_string(report.timestamp),
+                    report.app,
+                    report.event_type,
+                    report.fault_module,
+                    report.exception_code,
+                    if report.unknown_modules.is_empty() {
+                        String::from("нет")
+                    } else {
+                        report.unknown_modules.join(", ")
+                    },
+                    report.path
+                ))
+            })
+            .collect()
+    }
+}
+
+impl MiniDatWrapper for Pca {
+    fn new_instance(value: String) -> MiniDat {
+        MiniDat {
+            value: value,
+            id: "pca",
+        }
+    }
+}
+
+impl MiniDatEmployee<MiniDat> for Pca {
+    fn run() -> Vec<MiniDat> {
+        collect_pca_entries()
+            .par_iter()
+            .map(|entry| {
+                let mut value = format!(
+                    "{} | {} | {}",
+                    unix_to_string(entry.timestamp),
+                    entry.path,
+                    entry.source
+                );
+
+                if !entry.product.is_empty() || !entry.company.is_empty() {
+                    value.push_str(&format!(
+                        " | {} {} {}",
+                        entry.product, entry.company, entry.version
+                    ));
+                }
+
+                if !entry.exit_message.is_empty() {
+                    value.push_str(&format!(" | {}", entry.exit_message));
+                }
+
+                Pca::new_instance(value)
+            })
+            .collect()
+    }
+}
+
+impl MiniDatWrapper for MuiCache {
+    fn new_instance(value: String) -> MiniDat {
+        MiniDat {
+            value: value,
+            id: "mui_cache",
+        }
+    }
+}
+
+impl MiniDatEmployee<MiniDat> for MuiCache {
+    fn run() -> Vec<MiniDat> {
+        let mut response = vec![];
+
+        for user in collect_user_hives() {
+            if let Some(key) =
+                user.open_classes("Local Settings\\Software\\Microsoft\\Windows\\Shell\\MuiCache")
+            {
+                // имена значений вида C:\path\app.exe.FriendlyAppName
+                response.extend(key.values().into_iter().filter_map(|v| {
+                    let path = v.name.strip_suffix(".FriendlyAppName")?;
+
+                    Some(MuiCache::new_instance(format!(
+                        "{} | {} | {}",
+                        user.profile.name,
+                        path,
+                        v.as_string().unwrap_or_default()
+                    )))
+                }));
             }
-            let data = try_read_srum();
-            if data.len() != 0 {
-                return data
-                    .par_iter()
-                    .map(|item| SRUM::new_instance(item.to_owned()))
-                    .collect();
+        }
+
+        response
+    }
+}
+
+impl MiniDatWrapper for CompatibilityAssistant {
+    fn new_instance(value: String) -> MiniDat {
+        MiniDat {
+            value: value,
+            id: "compatibility_assistant",
+        }
+    }
+}
+
+impl MiniDatEmployee<MiniDat> for CompatibilityAssistant {
+    fn run() -> Vec<MiniDat> {
+        let mut response = vec![];
+
+        for user in collect_user_hives() {
+            if let Some(key) = user.open(
+                "SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion\\AppCompatFlags\\Compatibility Assistant\\Store",
+            ) {
+                response.extend(key.values().into_iter().map(|v| {
+                    CompatibilityAssistant::new_instance(format!(
+                        "{} | {}",
+                        user.profile.name, v.name
+                    ))
+                }));
             }
-            counter += 1;
-            thread::sleep(Duration::from_millis(500));
         }
+
+        response
     }
 }
 
-impl MiniDatWrapper for SevenZip {
+impl MiniDatWrapper for CompatibilityLayers {
     fn new_instance(value: String) -> MiniDat {
         MiniDat {
             value: value,
-            id: "seven_zip",
+            id: "compatibility_layers",
         }
     }
 }
 
-impl MiniDatEmployee<MiniDat> for SevenZip {
+impl MiniDatEmployee<MiniDat> for CompatibilityLayers {
     fn run() -> Vec<MiniDat> {
-        match CURRENT_USER.open("SOFTWARE\\7-Zip\\Compression") {
+        let mut layers: Vec<(String, String, String)> = vec![];
+
+        match LOCAL_MACHINE.open(COMPATIBILITY_LAYERS_KEY) {
             Ok(key) => {
-                let compression_history = key.get_value("ArcHistory");
-
-                if let Ok(compression_history) = compression_history {
-                    if compression_history.ty() == Type::Bytes {
-                        return bytes_to_string(compression_history.bytes(), false)
-                            .split('\0')
-                            .map(|s| s.trim())
-                            .filter(|s| !s.is_empty())
-                            .map(|path| SevenZip::new_instance(String::from(path)))
-                            .collect();
-                    }
-                } else {
-                    if cfg!(dev) {
-                        println!("{:?}", compression_history.err().unwrap());
+                if let Ok(values) = key.values() {
+                    for v in values {
+                        let value = key.get_string(&v.0).unwrap_or_default();
+                        layers.push((String::from("HKLM"), v.0, value));
                     }
                 }
             }
@@ -97,7 +821,241 @@ impl MiniDatEmployee<MiniDat> for SevenZip {
             }
         }
 
-        vec![]
+        for user in collect_user_hives() {
+            if let Some(key) = user.open(COMPATIBILITY_LAYERS_KEY) {
+                for v in key.values() {
+                    let value = v.as_string().unwrap_or_default();
+                    layers.push((user.profile.name.clone(), v.name, value));
+                }
+            }
+        }
+
+        layers
+            .into_iter()
+            .map(|(owner, path, value)| {
+                // лоадеры часто выставляют себе постоянный запуск от имени администратора
+                let admin = value
+                    .split_whitespace()
+                    .any(|layer| layer.eq_ignore_ascii_case("RUNASADMIN"));
+
+                CompatibilityLayers::new_instance(format!(
+                    "{} | {} | {}{}",
+                    path,
+                    value.trim_start_matches('~').trim(),
+                    owner,
+                    if admin {
+                        " | запуск от имени администратора"
+                    } else {
+                        ""
+                    }
+                ))
+            })
+            .collect()
+    }
+}
+
+impl MiniDatWrapper for RecycleBin {
+    fn new_instance(value: String) -> MiniDat {
+        MiniDat {
+            value: value,
+            id: "recycle_bin",
+        }
+    }
+}
+
+impl MiniDatEmployee<MiniDat> for RecycleBin {
+    fn run() -> Vec<MiniDat> {
+        collect_recycle_bin()
+            .par_iter()
+            .map(|item| {
+                RecycleBin::new_instance(format!(
+                    "{} | {} | {} байт | {} | {}",
+                    filetime_to_string(item.deleted),
+                    item.original_path,
+                    item.size,
+                    item.user,
+                    if item.payload_exists {
+                        &item.payload_path
+                    } else {
+                        "содержимое удалено"
+                    }
+                ))
+            })
+            .collect()
+    }
+}
+
+impl MiniDatWrapper for Timeline {
+    fn new_instance(value: String) -> MiniDat {
+        MiniDat {
+            value: value,
+            id: "timeline",
+        }
+    }
+}
+
+impl MiniDatEmployee<MiniDat> for Timeline {
+    fn run() -> Vec<MiniDat> {
+        collect_timeline()
+            .par_iter()
+            .map(|activity| {
+                Timeline::new_instance(format!(
+                    "{} | {} - {} | {} | {} | {} | в фокусе {} сек. | {}",
+                    activity.user,
+                    unix_to_string(activity.start),
+                    unix_to_string(activity.end),
+                    activity.app,
+                    activity.document,
+                    activity_type_name(activity.activity_type),
+                    activity.duration,
+                    activity.source
+                ))
+            })
+            .collect()
+    }
+}
+
+impl MiniDatWrapper for PowerShellHistory {
+    fn new_instance(value: String) -> MiniDat {
+        MiniDat {
+            value: value,
+            id: "powershell_history",
+        }
+    }
+}
+
+impl MiniDatEmployee<MiniDat> for PowerShellHistory {
+    fn run() -> Vec<MiniDat> {
+        collect_powershell_history()
+            .par_iter()
+            .map(|command| {
+                let mut value = format!("{} | {}", command.user, command.command);
+
+                if !command.reasons.is_empty() {
+                    value.push_str(&format!(" | {}", command.reasons.join(", ")));
+                }
+
+                PowerShellHistory::new_instance(value)
+            })
+            .collect()
+    }
+}
+
+impl MiniDatWrapper for Notifications {
+    fn new_instance(value: String) -> MiniDat {
+        MiniDat {
+            value: value,
+            id: "notifications",
+        }
+    }
+}
+
+impl MiniDatEmployee<MiniDat> for Notifications {
+    fn run() -> Vec<MiniDat> {
+        collect_notifications()
+            .par_iter()
+            .map(|notification| {
+                Notifications::new_instance(format!(
+                    "{} | {} | {} | {} | {}",
+                    notification.user,
+                    filetime_to_string(notification.arrival),
+                    notification.app,
+                    notification.text.join(" | "),
+                    notification.kind
+                ))
+            })
+            .collect()
+    }
+}
+
+impl MiniDatWrapper for RemoteSessions {
+    fn new_instance(value: String) -> MiniDat {
+        MiniDat {
+            value: value,
+            id: "remote_sessions",
+        }
+    }
+}
+
+impl MiniDatEmployee<MiniDat> for RemoteSessions {
+    fn run() -> Vec<MiniDat> {
+        collect_remote_sessions()
+            .par_iter()
+            .map(|session| {
+                RemoteSessions::new_instance(format!(
+                    "{} | {} | {} {} | {} | {} | {} | {}",
+                    if session.user.is_empty() {
+                        "система"
+                    } else {
+                        &session.user
+                    },
+                    session.tool,
+                    session.peer_id,
+                    session.peer_name,
+                    unix_to_string(session.start),
+                    match session.duration() {
+                        Some(duration) => format!("длительность {} сек.", duration),
+                        None => String::from("длительность неизвестна"),
+                    },
+                    session.status,
+                    session.source
+                ))
+            })
+            .collect()
+    }
+}
+
+impl MiniDatWrapper for HackTools {
+    fn new_instance(value: String) -> MiniDat {
+        MiniDat {
+            value: value,
+            id: "hack_tools",
+        }
+    }
+}
+
+impl MiniDatEmployee<MiniDat> for HackTools {
+    fn run() -> Vec<MiniDat> {
+        collect_hack_tool_traces()
+            .iter()
+            .map(|trace| {
+                HackTools::new_instance(format!(
+                    "{} | {} | {} | {} | {}",
+                    trace.tool,
+                    if trace.user.is_empty() {
+                        "система"
+                    } else {
+                        &trace.user
+                    },
+                    unix_to_string(trace.timestamp),
+                    trace.value,
+                    trace.source
+                ))
+            })
+            .collect()
+    }
+}
+
+impl MiniDatWrapper for SevenZip {
+    fn new_instance(value: String) -> MiniDat {
+        MiniDat {
+            value: value,
+            id: "seven_zip",
+        }
+    }
+}
+
+impl MiniDatEmployee<MiniDat> for SevenZip {
+    fn run() -> Vec<MiniDat> {
+        collect_archive_history("7zip")
+            .iter()
+            .map(|entry| {
+                SevenZip::new_instance(format!(
+                    "{} | {} | {}",
+                    entry.user, entry.action, entry.path
+                ))
+            })
+            .collect()
     }
 }
 
@@ -114,7 +1072,7 @@ impl MiniDatEmployee<MiniDat> for ShellBag {
     fn run() -> Vec<MiniDat> {
         collect_shell_bag()
             .iter()
-            .map(|item| ShellBag::new_instance(item.path.clone()))
+            .map(|item| ShellBag::new_instance(format!("{} | {}", item.user, item.path)))
             .collect()
     }
 }
@@ -130,27 +1088,23 @@ impl MiniDatWrapper for AppSwitched {
 
 impl MiniDatEmployee<MiniDat> for AppSwitched {
     fn run() -> Vec<MiniDat> {
-        match CURRENT_USER.open(
-            "SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Explorer\\FeatureUsage\\AppSwitched",
-        ) {
-            Ok(key) => {
-                let values = key.values();
-
-                if let Ok(values) = values {
-                    return values
-                        .map(|v| AppSwitched::new_instance(known_folder_in_path(v.0)))
-                        .collect();
-                }
-            }
-
-            Err(e) => {
-                if cfg!(dev) {
-                    println!("{e:?}");
-                }
+        let mut response = vec![];
+
+        for user in collect_user_hives() {
+            if let Some(key) = user.open(
+                "SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Explorer\\FeatureUsage\\AppSwitched",
+            ) {
+                response.extend(key.values().into_iter().map(|v| {
+                    AppSwitched::new_instance(format!(
+                        "{} | {}",
+                        user.profile.name,
+                        known_folder_in_path(v.name)
+                    ))
+                }));
             }
         }
 
-        vec![]
+        response
     }
 }
 
@@ -165,97 +1119,121 @@ impl MiniDatWrapper for WinRar {
 
 impl MiniDatEmployee<MiniDat> for WinRar {
     fn run() -> Vec<MiniDat> {
-        match CURRENT_USER.open("SOFTWARE\\WinRar\\ArcHistory") {
-            Ok(key) => {
-                let values = key.values();
-
-                if let Ok(values) = values {
-                    return values
-                        .map(|v| WinRar::new_instance(bytes_to_string(v.1.bytes(), true)))
-                        .collect();
-                }
-            }
+        collect_archive_history("winrar")
+            .iter()
+            .map(|entry| {
+                WinRar::new_instance(format!(
+                    "{} | {} | {}",
+                    entry.user, entry.action, entry.path
+                ))
+            })
+            .collect()
+    }
+}
 
-            Err(e) => {
-                if cfg!(dev) {
-                    println!("{e:?}");
-                }
-            }
+impl MiniDatWrapper for Bandizip {
+    fn new_instance(value: String) -> MiniDat {
+        MiniDat {
+            value: value,
+            id: "bandizip",
         }
+    }
+}
 
-        vec![]
+impl MiniDatEmployee<MiniDat> for Bandizip {
+    fn run() -> Vec<MiniDat> {
+        collect_archive_history("bandizip")
+            .iter()
+            .map(|entry| {
+                Bandizip::new_instance(format!(
+                    "{} | {} | {}",
+                    entry.user, entry.action, entry.path
+                ))
+            })
+            .collect()
     }
 }
 
-impl MiniDatWrapper for UserAssist {
+impl MiniDatWrapper for PeaZip {
     fn new_instance(value: String) -> MiniDat {
         MiniDat {
             value: value,
-            id: "user_assist",
+            id: "peazip",
         }
     }
 }
 
-impl MiniDatEmployee<MiniDat> for UserAssist {
+impl MiniDatEmployee<MiniDat> for PeaZip {
     fn run() -> Vec<MiniDat> {
-        match CURRENT_USER
-            .open("SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Explorer\\UserAssist")
-        {
-            Ok(key) => {
-                let keys = key.keys();
-
-                if let Ok(keys) = keys {
-                    let result: Vec<Option<Vec<MiniDat>>> = keys
-                        .map(|str_key| {
-                            match key.open(format!("{}\\Count", str_key)) {
-                                Ok(key) => {
-                                    let values = key.values();
-
-                                    if let Ok(values) = values {
-                                        return Some(
-                                            values
-                                                .map(|value| {
-                                                    UserAssist::new_instance(known_folder_in_path(
-                                                        rot13(&value.0),
-                                                    ))
-                                                })
-                                                .collect(),
-                                        );
-                                    }
-                                }
-
-                                Err(e) => {
-                                    if cfg!(dev) {
-                                        println!("{e:?}");
-                                    }
-                                }
-                            }
+        collect_archive_history("peazip")
+            .iter()
+            .map(|entry| {
+                PeaZip::new_instance(format!(
+                    "{} | {} | {}",
+                    entry.user, entry.action, entry.path
+                ))
+            })
+            .collect()
+    }
+}
 
-                            None
-                        })
-                        .collect();
+impl MiniDatWrapper for WinZip {
+    fn new_instance(value: String) -> MiniDat {
+        MiniDat {
+            value: value,
+            id: "winzip",
+        }
+    }
+}
 
-                    let mut response: Vec<MiniDat> = vec![];
+impl MiniDatEmployee<MiniDat> for WinZip {
+    fn run() -> Vec<MiniDat> {
+        collect_archive_history("winzip")
+            .iter()
+            .map(|entry| {
+                WinZip::new_instance(format!(
+                    "{} | {} | {}",
+                    entry.user, entry.action, entry.path
+                ))
+            })
+            .collect()
+    }
+}
 
-                    for m in result {
-                        if m.is_some() {
-                            let mut m = m.unwrap();
-                            response.append(&mut m);
-                        }
-                    }
+impl MiniDatWrapper for UserAssist {
+    fn new_instance(value: String) -> MiniDat {
+        MiniDat {
+            value: value,
+            id: "user_assist",
+        }
+    }
+}
 
-                    return response;
-                }
-            }
+impl MiniDatEmployee<MiniDat> for UserAssist {
+    fn run() -> Vec<MiniDat> {
+        let mut response = vec![];
 
-            Err(e) => {
-                if cfg!(dev) {
-                    println!("{e:?}");
+        for user in collect_user_hives() {
+            let Some(key) =
+                user.open("SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Explorer\\UserAssist")
+            else {
+                continue;
+            };
+
+            for str_key in key.keys() {
+                if let Some(key) = key.open(&format!("{}\\Count", str_key)) {
+                    response.extend(key.values().into_iter().map(|value| {
+                        UserAssist::new_instance(format!(
+                            "{} | {}",
+                            user.profile.name,
+                            known_folder_in_path(rot13(&value.name))
+                        ))
+                    }));
                 }
             }
         }
 
-        vec![]
+        response
     }
 }
 
@@ -454,40 +1432,52 @@ impl MiniDatWrapper for Bam {
 
 impl MiniDatEmployee<MiniDat> for Bam {
     fn run() -> Vec<MiniDat> {
-        let current_username_in_sid = get_current_username_in_sid();
+        let profiles = collect_user_profiles();
+
+        match LOCAL_MACHINE.open(format!(
+            "SYSTEM\\{}\\Services\\bam\\State\\UserSettings",
+            current_control_set()
+        )) {
+            Ok(settings) => match settings.keys() {
+                Ok(sids) => {
+                    let mut response = vec![];
+
+                    for sid in sids {
+                        let Ok(key) = settings.open(&sid) else {
+                            continue;
+                        };
 
-        if let Some(current_username_in_sid) = current_username_in_sid {
-            match LOCAL_MACHINE.open(format!(
-                "SYSTEM\\ControlSet001\\Services\\bam\\State\\UserSettings\\{}",
-                current_username_in_sid
-            )) {
-                Ok(key) => match key.values() {
-                    Ok(values) => {
-                        let mut response = vec![];
+                        let Ok(values) = key.values() else {
+                            continue;
+                        };
+
+                        let user = profile_name_by_sid(&profiles, &sid);
 
                         for value in values {
                             if value.0.starts_with(r"\Device\") {
-                                response.push(Bam::new_instance(
-                                    replace_device_path_with_drive_letter(&value.0),
-                                ));
+                                response.push(Bam::new_instance(format!(
+                                    "{} | {}",
+                                    user,
+                                    replace_device_path_with_drive_letter(&value.0)
+                                )));
                             }
                         }
-
-                        return response;
                     }
 
-                    Err(e) => {
-                        if cfg!(dev) {
-                            println!("{e:?}");
-                        }
-                    }
-                },
+                    return response;
+                }
 
                 Err(e) => {
                     if cfg!(dev) {
                         println!("{e:?}");
                     }
                 }
+            },
+
+            Err(e) => {
+                if cfg!(dev) {
+                    println!("{e:?}");
+                }
             }
         }
 
@@ -546,18 +1536,3 @@ fn get_drive_letter(device_path: &str) -> Option<String> {
 pub fn bytes_to_vec_u8(bytes: Bytes<&[u8]>) -> Vec<u8> {
     bytes.filter_map(|byte| byte.ok()).collect()
 }
-
-fn bytes_to_string(bytes: Bytes<&[u8]>, remove_null_char: bool) -> String {
-    let bytes: Vec<u8> = bytes_to_vec_u8(bytes);
-
-    let utf16_data: Vec<u16> = bytes
-        .chunks_exact(2)
-        .map(|chunk| u16::from_le_bytes([chunk[0], chunk[1]]))
-        .collect();
-
-    if remove_null_char {
-        String::from_utf16_lossy(&utf16_data).replace("\0", "")
-    } else {
-        String::from_utf16_lossy(&utf16_data)
-    }
-}
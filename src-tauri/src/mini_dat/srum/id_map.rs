@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ese::ese::EseDatabase,
+    mini_dat::registry_md::replace_device_path_with_drive_letter,
+    utils::{sid_bytes_to_string, utf16_bytes_to_string},
+};
+
+pub const ID_MAP_TABLE: &str = "SruDbIdMapTable";
+
+// https://github.com/libyal/esedb-kb/blob/main/documentation/System%20Resource%20Usage%20Monitor%20%28SRUM%29.asciidoc#311-idtype
+pub const ID_TYPE_APPLICATION: i64 = 0;
+pub const ID_TYPE_SERVICE: i64 = 1;
+pub const ID_TYPE_PACKAGE: i64 = 2;
+pub const ID_TYPE_USER_SID: i64 = 3;
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub enum SrumIdKind {
+    Win32,
+    Uwp,
+    Service,
+    Sid,
+}
+
+impl SrumIdKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            SrumIdKind::Win32 => "win32",
+            SrumIdKind::Uwp => "uwp",
+            SrumIdKind::Service => "служба",
+            SrumIdKind::Sid => "sid",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SrumId {
+    pub index: i64,
+    pub id_type: i64,
+    pub kind: SrumIdKind,
+    pub value: String,
+}
+
+impl SrumId {
+    pub fn decode(index: i64, id_type: i64, blob: &[u8]) -> Option<Self> {
+        let (kind, value) = match id_type {
+            ID_TYPE_APPLICATION => (SrumIdKind::Win32, application_name(blob)?),
+            ID_TYPE_SERVICE => (SrumIdKind::Service, non_empty(utf16_bytes_to_string(blob))?),
+            ID_TYPE_PACKAGE => (SrumIdKind::Uwp, non_empty(utf16_bytes_to_string(blob))?),
+            ID_TYPE_USER_SID => (SrumIdKind::Sid, sid_bytes_to_string(blob)?),
+            _ => return None,
+        };
+
+        Some(Self {
+            index,
+            id_type,
+            kind,
+            value,
+        })
+    }
+}
+
+fn non_empty(string: String) -> Option<String> {
+    (!string.is_empty()).then_some(string)
+}
+
+fn application_name(blob: &[u8]) -> Option<String> {
+    let string = non_empty(utf16_bytes_to_string(blob))?;
+
+    // пути в виде \Device\HarddiskVolumeN\...
+    if string.starts_with("\\Device\\") {
+        return Some(replace_device_path_with_drive_letter(&string));
+    }
+
+    // идентификатор программы вида !!cmd.exe!2019/03/19:09:13:17!0!
+    if let Some(rest) = string.strip_prefix("!!") {
+        return Some(rest.split('!').next().unwrap_or(rest).to_string());
+    }
+
+    Some(string)
+}
+
+pub fn read_id_map(db: &EseDatabase) -> HashMap<i64, SrumId> {
+    let mut ids = HashMap::new();
+
+    for record in db.read_table(ID_MAP_TABLE) {
+        let (Some(index), Some(id_type), Some(blob)) = (
+            record.get_i64("IdIndex"),
+            record.get_i64("IdType"),
+            record.get_bytes("IdBlob"),
+        ) else {
+            continue;
+        };
+
+        if let Some(id) = SrumId::decode(index, id_type, blob) {
+            ids.insert(index, id);
+        }
+    }
+
+    ids
+}
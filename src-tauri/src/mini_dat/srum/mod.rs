@@ -1,2 +1,2 @@
-pub mod session;
-pub mod provider;
\ No newline at end of file
+pub mod id_map;
+pub mod provider;
@@ -1,151 +1,159 @@
-use std::{ffi::c_void, mem::zeroed};
-
-use windows::Win32::Storage::{
-    Jet::{
-        JET_MoveFirst, JET_errSuccess, JetCloseTable, JetGetColumnInfoW, JetMove, JetOpenTableW,
-        JetRetrieveColumn, JET_COLUMNDEF,
-    },
-    StructuredStorage::JET_TABLEID,
+use std::{
+    collections::{BTreeSet, HashMap},
+    fs,
+    path::Path,
 };
 
+use serde::{Deserialize, Serialize};
+
 use crate::{
-    mini_dat::registry_md::replace_device_path_with_drive_letter,
-    utils::{random_name, string_to_pcwstr},
+    ese::{ese::EseDatabase, record::EseRecord},
+    utils::{copy_locked_file_to_temp, ole_date_to_string},
 };
 
-use super::session::JetSession;
+use super::id_map::{read_id_map, SrumId, SrumIdKind};
+
+pub const SRUM_PATH: &str = "C:\\Windows\\System32\\sru\\SRUDB.dat";
+
+pub const APP_RESOURCE_USAGE_TABLE: &str = "{D10CA2FE-6FCF-4F6D-848E-B2E99266FA89}";
+pub const NETWORK_DATA_USAGE_TABLE: &str = "{973F5D5C-1D90-4944-BE8E-24B94231A174}";
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SrumApp {
+    pub kind: SrumIdKind,
+    pub value: String,
+    pub users: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AppUsage {
+    pub timestamp: String,
+    pub app: String,
+    pub user: String,
+    pub foreground_cycle_time: i64,
+    pub bytes_read: i64,
+    pub bytes_written: i64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct NetworkUsage {
+    pub timestamp: String,
+    pub app: String,
+    pub user: String,
+    pub bytes_sent: i64,
+    pub bytes_received: i64,
+}
 
 // https://github.com/libyal/esedb-kb/blob/main/documentation/System%20Resource%20Usage%20Monitor%20%28SRUM%29.asciidoc
-pub fn try_read_srum() -> Vec<String> {
-    let mut strings = vec![];
-    let session = JetSession::open_instance(&random_name());
-    if let Some(mut session) = session {
-        if session.init() {
-            if session.begin() {
-                if session.attach_and_open_to_db("C:\\Windows\\System32\\sru\\SRUDB.dat") {
-                    unsafe {
-                        let table_name = "SruDbIdMapTable";
-                        let mut table_id: JET_TABLEID = zeroed();
-
-                        if JetOpenTableW(
-                            session.ses_id,
-                            session.dbid,
-                            string_to_pcwstr(String::from(table_name)).0,
-                            None,
-                            0,
-                            0,
-                            &mut table_id,
-                        ) == JET_errSuccess
-                        {
-                            let id_type_colid: u32 = find_id_column_by_name(
-                                session.clone(),
-                                String::from(table_name),
-                                String::from("IdType"),
-                            );
-
-                            let id_blob_colid: u32 = find_id_column_by_name(
-                                session.clone(),
-                                String::from(table_name),
-                                String::from("IdBlob"),
-                            );
-
-                            let mut err =
-                                JetMove(session.ses_id, table_id, JET_MoveFirst as i32, 0);
-                            while err == JET_errSuccess {
-                                let mut id_type: u8 = 0;
-                                let mut cb_actual: u32 = 0;
-                                let _ = JetRetrieveColumn(
-                                    session.ses_id,
-                                    table_id,
-                                    id_type_colid,
-                                    Some(&mut id_type as *mut u8 as *mut _),
-                                    size_of::<u8>() as u32,
-                                    Some(&mut cb_actual),
-                                    0,
-                                    None,
-                                );
-
-                                let max_blob_size = 256;
-                                let mut blob_buffer: Vec<u16> = vec![0u16; max_blob_size];
-                                let mut cb_actual_blob: u32 = 0;
-                                let _ = JetRetrieveColumn(
-                                    session.ses_id,
-                                    table_id,
-                                    id_blob_colid,
-                                    Some(blob_buffer.as_mut_ptr() as *mut _),
-                                    max_blob_size as u32,
-                                    Some(&mut cb_actual_blob),
-                                    0,
-                                    None,
-                                );
-
-                                blob_buffer.truncate(cb_actual_blob as usize);
-
-                                // i'm trying getting IdType is unsigned byte...
-                                // i was looking at the documentation (https://github.com/libyal/esedb-kb/blob/main/documentation/System%20Resource%20Usage%20Monitor%20%28SRUM%29.asciidoc#311-idtype)
-                                // i saw there types 0,1,2,3, the query returns a different value altogether
-                                match id_type {
-                                    33 => {
-                                        let string = String::from_utf16_lossy(&blob_buffer);
-                                        for line in string.lines() {
-                                            let line = line.trim();
-                                            if line.is_empty() {
-                                                continue;
-                                            }
-                                            let line = if line.starts_with("!!") {
-                                                &line[2..]
-                                            } else {
-                                                line
-                                            };
-                                            if let Some(pos) = line.find('!') {
-                                                let filename = &line[..pos];
-                                                strings
-                                                    .push(String::from(filename).replace('\0', ""));
-                                            }
-                                        }
-                                    }
-                                    92 => {
-                                        strings.push(
-                                            replace_device_path_with_drive_letter(
-                                                &String::from_utf16_lossy(&blob_buffer),
-                                            )
-                                            .replace('\0', ""),
-                                        );
-                                    }
-                                    77 => {
-                                        // skipping (windows apps)
-                                    }
-                                    _ => {}
-                                }
-
-                                err = JetMove(session.ses_id, table_id, 2, 0);
-                            }
-
-                            let _ = JetCloseTable(session.ses_id, table_id);
-                        }
-                    }
-                }
+pub struct Srum {
+    ids: HashMap<i64, SrumId>,
+    app_records: Vec<EseRecord>,
+    network_records: Vec<EseRecord>,
+}
+
+impl Srum {
+    pub fn open(path: &str) -> Option<Self> {
+        // служба SRUM держит базу открытой, поэтому читаем ее копию
+        let temporary = copy_locked_file_to_temp(Path::new(path))?;
+
+        let srum = EseDatabase::open(&temporary).map(|db| Self {
+            ids: read_id_map(&db),
+            app_records: db.read_table(APP_RESOURCE_USAGE_TABLE),
+            network_records: db.read_table(NETWORK_DATA_USAGE_TABLE),
+        });
+
+        let _ = fs::remove_file(&temporary);
+
+        srum
+    }
+
+    // все записи карты идентификаторов кроме sid, с пользователями из таблиц использования
+    pub fn apps(&self) -> Vec<SrumApp> {
+        let mut users: HashMap<i64, BTreeSet<String>> = HashMap::new();
+
+        for record in self.app_records.iter().chain(self.network_records.iter()) {
+            if let Some(app_id) = record.get_i64("AppId") {
+                users
+                    .entry(app_id)
+                    .or_default()
+                    .insert(self.resolve(record, "UserId"));
             }
         }
 
-        session.close();
+        let mut response: Vec<SrumApp> = self
+            .ids
+            .values()
+            .filter(|id| id.kind != SrumIdKind::Sid)
+            .map(|id| SrumApp {
+                kind: id.kind.clone(),
+                value: id.value.clone(),
+                users: users
+                    .get(&id.index)
+                    .map(|set| set.iter().cloned().collect())
+                    .unwrap_or_default(),
+            })
+            .collect();
+
+        response.sort_by(|a, b| a.value.cmp(&b.value));
+        response
     }
 
-    strings
-}
-
-unsafe fn find_id_column_by_name(session: JetSession, table_name: String, name: String) -> u32 {
-    let mut buf: JET_COLUMNDEF = zeroed();
+    pub fn app_usage(&self) -> Vec<AppUsage> {
+        let mut response: Vec<(f64, AppUsage)> = self
+            .app_records
+            .iter()
+            .map(|record| {
+                let time = record.get_f64("TimeStamp").unwrap_or(0.0);
+
+                (
+                    time,
+                    AppUsage {
+                        timestamp: ole_date_to_string(time),
+                        app: self.resolve(record, "AppId"),
+                        user: self.resolve(record, "UserId"),
+                        foreground_cycle_time: record.get_i64("ForegroundCycleTime").unwrap_or(0),
+                        bytes_read: record.get_i64("ForegroundBytesRead").unwrap_or(0)
+                            + record.get_i64("BackgroundBytesRead").unwrap_or(0),
+                        bytes_written: record.get_i64("ForegroundBytesWritten").unwrap_or(0)
+                            + record.get_i64("BackgroundBytesWritten").unwrap_or(0),
+                    },
+                )
+            })
+            .collect();
+
+        response.sort_by(|a, b| a.0.total_cmp(&b.0));
+        response.into_iter().map(|(_, usage)| usage).collect()
+    }
 
-    let _ = JetGetColumnInfoW(
-        session.ses_id,
-        session.dbid,
-        string_to_pcwstr(table_name).0,
-        Some(string_to_pcwstr(name).0),
-        &mut buf as *mut _ as *mut c_void,
-        size_of::<JET_COLUMNDEF>() as u32,
-        1,
-    );
+    pub fn network_usage(&self) -> Vec<NetworkUsage> {
+        let mut response: Vec<(f64, NetworkUsage)> = self
+            .network_records
+            .iter()
+            .map(|record| {
+                let time = record.get_f64("TimeStamp").unwrap_or(0.0);
+
+                (
+                    time,
+                    NetworkUsage {
+                        timestamp: ole_date_to_string(time),
+                        app: self.resolve(record, "AppId"),
+                        user: self.resolve(record, "UserId"),
+                        bytes_sent: record.get_i64("BytesSent").unwrap_or(0),
+                        bytes_received: record.get_i64("BytesRecvd").unwrap_or(0),
+                    },
+                )
+            })
+            .collect();
+
+        response.sort_by(|a, b| a.0.total_cmp(&b.0));
+        response.into_iter().map(|(_, usage)| usage).collect()
+    }
 
-    buf.columnid
+    fn resolve(&self, record: &EseRecord, column: &str) -> String {
+        record
+            .get_i64(column)
+            .and_then(|id| self.ids.get(&id))
+            .map(|id| id.value.clone())
+            .unwrap_or_else(|| String::from("undefined"))
+    }
 }
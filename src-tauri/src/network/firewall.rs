@@ -0,0 +1,163 @@
+use serde::{Deserialize, Serialize};
+use windows_registry::LOCAL_MACHINE;
+
+const FIREWALL_RULES_KEYS: [&str; 2] = [
+    "SYSTEM\\CurrentControlSet\\Services\\SharedAccess\\Parameters\\FirewallPolicy\\FirewallRules",
+    "SOFTWARE\\Policies\\Microsoft\\WindowsFirewall\\FirewallRules",
+];
+
+// игра, клиент steam и античиты, которым блокируют доступ к серверам
+const WATCHED_APPS: [&str; 12] = [
+    "cs2.exe",
+    "steam.exe",
+    "steamservice.exe",
+    "steamwebhelper.exe",
+    "faceit.exe",
+    "faceitclient.exe",
+    "faceitservice.exe",
+    "eseaclient.exe",
+    "esea.exe",
+    "easyanticheat.exe",
+    "beservice.exe",
+    "vgc.exe",
+];
+
+const WATCHED_HOSTS: [&str; 11] = [
+    "steampowered.com",
+    "steamcommunity.com",
+    "steamcontent.com",
+    "steamserver.net",
+    "steamstatic.com",
+    "valvesoftware.com",
+    "faceit.com",
+    "faceit-cdn.net",
+    "esea.net",
+    "easyanticheat.net",
+    "battleye.com",
+];
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct FirewallRule {
+    pub id: String,
+    pub name: String,
+    pub app: String,
+    pub service: String,
+    pub direction: String,
+    pub action: String,
+    pub active: bool,
+    pub protocol: String,
+    pub remote_addresses: Vec<String>,
+    pub remote_ports: Vec<String>,
+    pub watched: bool,
+    pub policy: bool,
+}
+
+pub fn collect_firewall_rules() -> Vec<FirewallRule> {
+    let mut rules = vec![];
+
+    for (path, policy) in FIREWALL_RULES_KEYS.iter().zip([false, true]) {
+        let Ok(key) = LOCAL_MACHINE.open(path) else {
+            continue;
+        };
+
+        let Ok(values) = key.values() else {
+            continue;
+        };
+
+        for (id, _) in values {
+            let Ok(value) = key.get_string(&id) else {
+                continue;
+            };
+
+            let mut rule = parse_rule(&value);
+            rule.id = id;
+            rule.policy = policy;
+
+            // встроенные правила windows названы ссылками на ресурсы вида @FirewallAPI.dll,-1
+            if rule.watched || !rule.name.starts_with('@') {
+                rules.push(rule);
+            }
+        }
+    }
+
+    rules
+}
+
+// v2.30|Action=Block|Active=TRUE|Dir=Out|Protocol=6|App=C:\game\cs2.exe|RA4=1.2.3.4|Name=rule|
+pub fn parse_rule(value: &str) -> FirewallRule {
+    let mut rule = FirewallRule::default();
+
+    for field in value.split('|') {
+        let Some((name, value)) = field.split_once('=') else {
+            continue;
+        };
+
+        match name {
+            "Name" => rule.name = value.to_string(),
+            "App" => rule.app = value.to_string(),
+            "Svc" => rule.service = value.to_string(),
+            "Dir" => rule.direction = value.to_string(),
+            "Action" => rule.action = value.to_string(),
+            "Active" => rule.active = value.eq_ignore_ascii_case("true"),
+            "Protocol" => rule.protocol = value.to_string(),
+            "RPort" | "RPort2_10" => rule.remote_ports.push(value.to_string()),
+            // RA4, RA6, RA42 и RA62 хранят адреса и диапазоны
+            _ if name.starts_with("RA") => rule.remote_addresses.push(value.to_string()),
+            _ => {}
+        }
+    }
+
+    let app = rule.app.to_lowercase();
+    rule.watched = WATCHED_APPS
+        .iter()
+        .any(|watched| app.ends_with(&format!("\\{}", watched)) || app.eq(watched));
+
+    rule
+}
+
+pub fn is_watched_host(host: &str) -> bool {
+    let host = host.to_lowercase();
+
+    WATCHED_HOSTS
+        .iter()
+        .any(|watched| host.eq(watched) || host.ends_with(&format!(".{}", watched)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_rule() {
+        let rule = parse_rule(
+            "v2.30|Action=Block|Active=TRUE|Dir=Out|Protocol=6|RPort=27015|\
+             App=C:\\Games\\Counter-Strike Global Offensive\\game\\bin\\win64\\CS2.exe|\
+             RA4=1.2.3.4|RA42=10.0.0.0/8|Name=rule|",
+        );
+
+        assert_eq!(rule.name, "rule");
+        assert_eq!(rule.action, "Block");
+        assert_eq!(rule.direction, "Out");
+        assert_eq!(rule.protocol, "6");
+        assert!(rule.active);
+        assert_eq!(rule.remote_ports, ["27015"]);
+        assert_eq!(rule.remote_addresses, ["1.2.3.4", "10.0.0.0/8"]);
+        assert!(rule.watched);
+    }
+
+    #[test]
+    fn ignores_unrelated_apps() {
+        // cs2.exe должен быть именем файла, а не концом другого имени
+        let rule = parse_rule("v2.30|Action=Block|App=C:\\tools\\notcs2.exe|");
+
+        assert!(!rule.watched);
+        assert!(!rule.active);
+    }
+
+    #[test]
+    fn matches_watched_hosts() {
+        assert!(is_watched_host("STEAMCOMMUNITY.COM"));
+        assert!(is_watched_host("cdn.faceit-cdn.net"));
+        assert!(!is_watched_host("notsteampowered.com"));
+    }
+}
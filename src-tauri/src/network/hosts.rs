@@ -0,0 +1,80 @@
+use std::fs;
+
+use serde::{Deserialize, Serialize};
+
+use crate::utils::{get_windows_dir, text_bytes_to_string};
+
+use super::firewall::is_watched_host;
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct HostsEntry {
+    pub address: String,
+    pub host: String,
+    pub watched: bool,
+}
+
+pub fn collect_hosts() -> Vec<HostsEntry> {
+    let path = format!("{}\\System32\\drivers\\etc\\hosts", get_windows_dir());
+
+    match fs::read(&path) {
+        Ok(data) => parse_hosts(&text_bytes_to_string(&data)),
+        Err(e) => {
+            if cfg!(dev) {
+                println!("{e:?}");
+            }
+
+            vec![]
+        }
+    }
+}
+
+// стандартный hosts содержит только комментарии, поэтому выводим все записи кроме localhost
+pub fn parse_hosts(text: &str) -> Vec<HostsEntry> {
+    let mut entries = vec![];
+
+    for line in text.lines() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        let mut parts = line.split_whitespace();
+
+        let Some(address) = parts.next() else {
+            continue;
+        };
+
+        for host in parts {
+            if host.eq_ignore_ascii_case("localhost") {
+                continue;
+            }
+
+            entries.push(HostsEntry {
+                address: address.to_string(),
+                host: host.to_string(),
+                watched: is_watched_host(host),
+            });
+        }
+    }
+
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_hosts_entries() {
+        let text = "# Copyright (c) 1993-2009 Microsoft Corp.\r\n\
+                    #\t127.0.0.1       localhost\r\n\
+                    127.0.0.1 localhost\r\n\
+                    \r\n\
+                    0.0.0.0 api.steampowered.com   example.com # блокировка\r\n";
+
+        let entries = parse_hosts(text);
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].address, "0.0.0.0");
+        assert_eq!(entries[0].host, "api.steampowered.com");
+        assert!(entries[0].watched);
+        assert_eq!(entries[1].host, "example.com");
+        assert!(!entries[1].watched);
+    }
+}
@@ -0,0 +1,113 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    profiles::profiles::collect_user_profiles,
+    utils::{copy_sqlite_to_temp, remove_sqlite_temp, xml_tag_values, xml_unescape},
+};
+
+const NOTIFICATIONS_DB: &str = "Microsoft\\Windows\\Notifications\\wpndatabase.db";
+
+const NOTIFICATIONS_QUERY: &str = "SELECT NotificationHandler.PrimaryId, Notification.Type, \
+    Notification.Payload, Notification.ArrivalTime FROM Notification \
+    LEFT JOIN NotificationHandler ON Notification.HandlerId = NotificationHandler.RecordId";
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Notification {
+    pub user: String,
+    pub app: String,
+    pub kind: String,
+    pub text: Vec<String>,
+    pub arrival: i64,
+}
+
+pub fn collect_notifications() -> Vec<Notification> {
+    collect_user_profiles()
+        .iter()
+        .flat_map(|profile| {
+            read_notifications(&profile.local_app_data().join(NOTIFICATIONS_DB))
+                .into_iter()
+                .map(|mut notification| {
+                    notification.user = profile.name.clone();
+                    notification
+                })
+                .collect::<Vec<Notification>>()
+        })
+        .collect()
+}
+
+pub fn read_notifications(path: &Path) -> Vec<Notification> {
+    if !path.exists() {
+        return vec![];
+    }
+
+    let Some(temporary) = copy_sqlite_to_temp(path) else {
+        return vec![];
+    };
+
+    let mut response = vec![];
+
+    match sqlite::open(&temporary) {
+        Ok(connection) => match connection.prepare(NOTIFICATIONS_QUERY) {
+            Ok(statement) => {
+                for row in statement.into_iter().filter_map(Result::ok) {
+                    let app = row.try_read::<Option<&str>, _>(0).ok().flatten();
+                    let kind = row.try_read::<Option<&str>, _>(1).ok().flatten();
+
+                    // payload хранится как blob, но в старых версиях бывает текстом
+                    let payload = match row.try_read::<Option<&[u8]>, _>(2) {
+                        Ok(payload) => {
+                            String::from_utf8_lossy(payload.unwrap_or_default()).to_string()
+                        }
+                        Err(_) => row
+                            .try_read::<Option<&str>, _>(2)
+                            .ok()
+                            .flatten()
+                            .unwrap_or_default()
+                            .to_string(),
+                    };
+
+                    let text = parse_payload(&payload);
+
+                    if text.is_empty() {
+                        continue;
+                    }
+
+                    response.push(Notification {
+                        user: String::new(),
+                        app: app.unwrap_or_default().to_string(),
+                        kind: kind.unwrap_or_default().to_string(),
+                        text,
+                        arrival: row.try_read::<i64, _>(3).unwrap_or(0),
+                    });
+                }
+            }
+
+            Err(e) => {
+                if cfg!(dev) {
+                    println!("{e:?}");
+                }
+            }
+        },
+
+        Err(e) => {
+            if cfg!(dev) {
+                println!("{e:?}");
+            }
+        }
+    }
+
+    remove_sqlite_temp(&temporary);
+
+    response
+}
+
+// <toast><visual><binding template="ToastGeneric"><text>заголовок</text><text>сообщение</text>...
+pub fn parse_payload(payload: &str) -> Vec<String> {
+    xml_tag_values(payload, "text")
+        .iter()
+        .map(|text| xml_unescape(text.trim()))
+        .filter(|text| !text.is_empty())
+        .collect()
+}
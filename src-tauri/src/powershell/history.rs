@@ -0,0 +1,108 @@
+use std::{fs, path::Path};
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::{profiles::profiles::collect_user_profiles, utils::text_bytes_to_string};
+
+const PSREADLINE_DIR: &str = "AppData\\Roaming\\Microsoft\\Windows\\PowerShell\\PSReadLine";
+
+// ConsoleHost_history.txt, а также истории других хостов (vscode и т.д.)
+const HISTORY_SUFFIX: &str = "_history.txt";
+
+const SUSPICIOUS_COMMANDS: [(&str, &str); 5] = [
+    (
+        r"(?i)\b(iwr|irm|wget|curl|invoke-webrequest|invoke-restmethod|start-bitstransfer|downloadstring|downloadfile|downloaddata)\b",
+        "загрузка из сети",
+    ),
+    (
+        r"(?i)\b(set-mppreference|add-mppreference)\b",
+        "изменение настроек Защитника",
+    ),
+    (r"(?i)\bfsutil(\.exe)?\s+usn\b", "операции с журналом USN"),
+    (
+        r"(?i)\b(wevtutil(\.exe)?\s+(cl|clear-log)|clear-eventlog|remove-eventlog|limit-eventlog)\b",
+        "очистка журналов событий",
+    ),
+    (
+        r"(?i)\b(iex|invoke-expression)\b",
+        "выполнение строки как кода",
+    ),
+];
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct HistoryCommand {
+    pub user: String,
+    pub file: String,
+    pub line: usize,
+    pub command: String,
+    pub reasons: Vec<String>,
+}
+
+pub fn collect_powershell_history() -> Vec<HistoryCommand> {
+    let patterns: Vec<(Regex, &'static str)> = SUSPICIOUS_COMMANDS
+        .iter()
+        .filter_map(|(pattern, reason)| Some((Regex::new(pattern).ok()?, *reason)))
+        .collect();
+
+    collect_user_profiles()
+        .into_iter()
+        .flat_map(|profile| {
+            let Ok(files) = fs::read_dir(Path::new(&profile.path).join(PSREADLINE_DIR)) else {
+                return vec![];
+            };
+
+            files
+                .filter_map(Result::ok)
+                .filter(|file| {
+                    file.file_name()
+                        .to_string_lossy()
+                        .to_lowercase()
+                        .ends_with(&HISTORY_SUFFIX.to_lowercase())
+                })
+                .flat_map(|file| {
+                    let data = match fs::read(file.path()) {
+                        Ok(data) => data,
+                        Err(e) => {
+                            if cfg!(dev) {
+                                println!("{e:?}");
+                            }
+
+                            return vec![];
+                        }
+                    };
+
+                    parse_history(
+                        &text_bytes_to_string(&data),
+                        &profile.name,
+                        &file.file_name().to_string_lossy(),
+                        &patterns,
+                    )
+                })
+                .collect()
+        })
+        .collect()
+}
+
+pub fn parse_history(
+    text: &str,
+    user: &str,
+    file: &str,
+    patterns: &[(Regex, &'static str)],
+) -> Vec<HistoryCommand> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| HistoryCommand {
+            user: user.to_string(),
+            file: file.to_string(),
+            line: index + 1,
+            command: line.trim().to_string(),
+            reasons: patterns
+                .iter()
+                .filter(|(regex, _)| regex.is_match(line))
+                .map(|(_, reason)| reason.to_string())
+                .collect(),
+        })
+        .collect()
+}
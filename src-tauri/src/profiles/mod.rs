@@ -0,0 +1,2 @@
+pub mod profiles;
+pub mod user_hive;
@@ -0,0 +1,71 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use windows_registry::LOCAL_MACHINE;
+
+use crate::utils::{expand_environment_strings, get_current_username_in_sid};
+
+const PROFILE_LIST_KEY: &str = "SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion\\ProfileList";
+
+// локальные и доменные учетные записи, системные профили (S-1-5-18 и т.д.) пропускаем
+const USER_SID_PREFIX: &str = "S-1-5-21-";
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct UserProfile {
+    pub sid: String,
+    pub name: String,
+    pub path: String,
+    pub current: bool,
+}
+
+impl UserProfile {
+    pub fn app_data(&self) -> PathBuf {
+        Path::new(&self.path).join("AppData\\Roaming")
+    }
+
+    pub fn local_app_data(&self) -> PathBuf {
+        Path::new(&self.path).join("AppData\\Local")
+    }
+}
+
+pub fn collect_user_profiles() -> Vec<UserProfile> {
+    let key = match LOCAL_MACHINE.open(PROFILE_LIST_KEY) {
+        Ok(key) => key,
+        Err(e) => {
+            if cfg!(dev) {
+                println!("{e:?}");
+            }
+
+            return vec![];
+        }
+    };
+
+    let Ok(sids) = key.keys() else {
+        return vec![];
+    };
+
+    let current_sid = get_current_username_in_sid().unwrap_or_default();
+
+    sids.filter(|sid| sid.starts_with(USER_SID_PREFIX))
+        .filter_map(|sid| {
+            let path = key.open(&sid).ok()?.get_string("ProfileImagePath").ok()?;
+            let path = expand_environment_strings(&path);
+            let name = path.rsplit('\\').next().unwrap_or_default().to_string();
+
+            Some(UserProfile {
+                current: sid.eq(&current_sid),
+                sid,
+                name,
+                path,
+            })
+        })
+        .collect()
+}
+
+pub fn profile_name_by_sid(profiles: &[UserProfile], sid: &str) -> String {
+    profiles
+        .iter()
+        .find(|profile| profile.sid.eq(sid))
+        .map(|profile| profile.name.clone())
+        .unwrap_or_else(|| sid.to_string())
+}
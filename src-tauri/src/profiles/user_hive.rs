@@ -0,0 +1,167 @@
+use std::path::Path;
+
+use windows::Win32::System::Registry::HKEY_USERS;
+use windows_registry::{Key, Type, USERS};
+
+use crate::{
+    regf::hive::{
+        Hive, HiveKey, HiveValue, REG_BINARY, REG_DWORD, REG_EXPAND_SZ, REG_MULTI_SZ, REG_QWORD,
+        REG_SZ,
+    },
+    utils::get_key_last_write_time,
+};
+
+use super::profiles::{collect_user_profiles, UserProfile};
+
+const NTUSER_FILE: &str = "NTUSER.DAT";
+const USRCLASS_FILE: &str = "AppData\\Local\\Microsoft\\Windows\\UsrClass.dat";
+
+pub enum HiveSource {
+    Live(Key),
+    Offline(Hive),
+}
+
+// кусты NTUSER.DAT и UsrClass.dat одного пользователя
+pub struct UserHive {
+    pub profile: UserProfile,
+    ntuser: Option<HiveSource>,
+    classes: Option<HiveSource>,
+}
+
+pub enum RegKey<'a> {
+    Live(Key),
+    Offline(HiveKey<'a>),
+}
+
+impl UserHive {
+    pub fn load(profile: UserProfile) -> Self {
+        let path = Path::new(&profile.path);
+
+        Self {
+            ntuser: open_source(&profile.sid, &path.join(NTUSER_FILE)),
+            classes: open_source(
+                &format!("{}_Classes", profile.sid),
+                &path.join(USRCLASS_FILE),
+            ),
+            profile,
+        }
+    }
+
+    // путь относительно HKEY_CURRENT_USER
+    pub fn open(&self, path: &str) -> Option<RegKey<'_>> {
+        open_in(self.ntuser.as_ref()?, path)
+    }
+
+    // время последнего изменения раздела относительно HKEY_CURRENT_USER в формате FILETIME
+    pub fn last_write(&self, path: &str) -> i64 {
+        match self.ntuser.as_ref() {
+            Some(HiveSource::Live(_)) => {
+                get_key_last_write_time(HKEY_USERS, &format!("{}\\{}", self.profile.sid, path))
+            }
+            Some(HiveSource::Offline(hive)) => {
+                hive.open_key(path).map(|key| key.last_write).unwrap_or(0)
+            }
+            None => 0,
+        }
+    }
+
+    // путь относительно HKEY_CURRENT_USER\Software\Classes
+    pub fn open_classes(&self, path: &str) -> Option<RegKey<'_>> {
+        open_in(self.classes.as_ref()?, path)
+    }
+}
+
+impl<'a> RegKey<'a> {
+    pub fn open(&self, path: &str) -> Option<RegKey<'a>> {
+        match self {
+            RegKey::Live(key) => key.open(path).ok().map(RegKey::Live),
+            RegKey::Offline(key) => key.open(path).map(RegKey::Offline),
+        }
+    }
+
+    pub fn keys(&self) -> Vec<String> {
+        match self {
+            RegKey::Live(key) => key.keys().map(|keys| keys.collect()).unwrap_or_default(),
+            RegKey::Offline(key) => key.keys().into_iter().map(|key| key.name).collect(),
+        }
+    }
+
+    pub fn values(&self) -> Vec<HiveValue> {
+        match self {
+            RegKey::Live(key) => key
+                .values()
+                .map(|values| {
+                    values
+                        .map(|(name, value)| HiveValue {
+                            name,
+                            value_type: value_type(value.ty()),
+                            data: value.to_vec(),
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+            RegKey::Offline(key) => key.values(),
+        }
+    }
+
+    pub fn value(&self, name: &str) -> Option<HiveValue> {
+        match self {
+            RegKey::Live(key) => key.get_value(name).ok().map(|value| HiveValue {
+                name: name.to_string(),
+                value_type: value_type(value.ty()),
+                data: value.to_vec(),
+            }),
+            RegKey::Offline(key) => key.value(name),
+        }
+    }
+
+    pub fn get_string(&self, name: &str) -> Option<String> {
+        self.value(name)?.as_string()
+    }
+}
+
+pub fn collect_user_hives() -> Vec<UserHive> {
+    collect_user_profiles()
+        .into_iter()
+        .map(UserHive::load)
+        .collect()
+}
+
+// куст вошедшего пользователя загружен в HKEY_USERS и заблокирован,
+// кусты остальных пользователей разбираем напрямую из файлов
+fn open_source(name: &str, file: &Path) -> Option<HiveSource> {
+    if let Ok(key) = USERS.open(name) {
+        return Some(HiveSource::Live(key));
+    }
+
+    if !file.exists() {
+        return None;
+    }
+
+    let hive = Hive::open(&file.to_string_lossy())?;
+
+    // журналы старого формата не применяются, последних изменений в таком кусте может не быть
+    if hive.dirty && cfg!(dev) {
+        println!("dirty hive: {}", file.to_string_lossy());
+    }
+
+    Some(HiveSource::Offline(hive))
+}
+
+fn open_in<'a>(source: &'a HiveSource, path: &str) -> Option<RegKey<'a>> {
+    match source {
+        HiveSource::Live(key) => key.open(path).ok().map(RegKey::Live),
+        HiveSource::Offline(hive) => hive.open_key(path).map(RegKey::Offline),
+    }
+}
+
+fn value_type(ty: Type) -> u32 {
+    match ty {
+        Type::U32 => REG_DWORD,
+        Type::U64 => REG_QWORD,
+        Type::String => REG_SZ,
+        Type::ExpandString => REG_EXPAND_SZ,
+        Type::MultiString => REG_MULTI_SZ,
+        _ => REG_BINARY,
+    }
+}
@@ -0,0 +1 @@
+pub mod recycle_bin;
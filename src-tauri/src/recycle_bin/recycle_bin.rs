@@ -0,0 +1,170 @@
+use std::{fs, path::Path};
+
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    analyzer::{analyzer::Analyzer, context::AnalyzerContext},
+    profiles::profiles::{collect_user_profiles, profile_name_by_sid},
+    usn_journal::volume::get_all_volumes,
+    utils::{get_parallel_files, utf16_bytes_to_string},
+};
+
+const RECYCLE_BIN_DIR: &str = "$Recycle.Bin";
+
+// заголовок $I: версия, размер и время удаления
+const HEADER_SIZE: usize = 24;
+// в первой версии путь хранится в буфере фиксированной длины MAX_PATH
+const V1_PATH_SIZE: usize = 520;
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RecycleBinItem {
+    pub sid: String,
+    pub user: String,
+    pub index_path: String,
+    pub payload_path: String,
+    pub original_path: String,
+    pub size: u64,
+    pub deleted: i64,
+    pub version: u64,
+    pub payload_exists: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct RecycleBinIndex {
+    pub version: u64,
+    pub size: u64,
+    pub deleted: i64,
+    pub path: String,
+}
+
+pub fn collect_recycle_bin() -> Vec<RecycleBinItem> {
+    let profiles = collect_user_profiles();
+
+    get_all_volumes()
+        .iter()
+        .flat_map(|volume| read_recycle_bin(&Path::new(&volume.path).join(RECYCLE_BIN_DIR)))
+        .map(|mut item| {
+            item.user = profile_name_by_sid(&profiles, &item.sid);
+            item
+        })
+        .collect()
+}
+
+// содержимое корзины в виде контекста анализатора, чтобы сверить удаленные файлы с базой
+pub fn collect_recycle_bin_context() -> Option<AnalyzerContext> {
+    let files: Vec<(String, String)> = collect_recycle_bin()
+        .into_iter()
+        .filter(|item| item.payload_exists)
+        .flat_map(|item| {
+            let name = item
+                .original_path
+                .rsplit('\\')
+                .next()
+                .unwrap_or_default()
+                .to_string();
+
+            // удаленная папка хранится в $R целиком
+            if Path::new(&item.payload_path).is_dir() {
+                get_parallel_files(item.payload_path)
+                    .into_iter()
+                    .map(|file| {
+                        let name = file.rsplit('\\').next().unwrap_or_default().to_string();
+                        (name, file)
+                    })
+                    .collect()
+            } else {
+                vec![(name, item.payload_path)]
+            }
+        })
+        .collect();
+
+    let items: Vec<_> = files
+        .par_iter()
+        .filter_map(|(name, path)| Analyzer::create_file_context(name.clone(), path.clone(), true))
+        .collect();
+
+    (!items.is_empty()).then_some(AnalyzerContext { items })
+}
+
+pub fn read_recycle_bin(dir: &Path) -> Vec<RecycleBinItem> {
+    let Ok(users) = fs::read_dir(dir) else {
+        return vec![];
+    };
+
+    users
+        .filter_map(Result::ok)
+        .filter(|user| user.path().is_dir())
+        .flat_map(|user| {
+            let sid = user.file_name().to_string_lossy().to_string();
+
+            let Ok(files) = fs::read_dir(user.path()) else {
+                return vec![];
+            };
+
+            files
+                .filter_map(Result::ok)
+                .filter_map(|file| {
+                    let name = file.file_name().to_string_lossy().to_string();
+                    let suffix = name.strip_prefix("$I")?;
+                    let index = match fs::read(file.path()) {
+                        Ok(data) => parse_index(&data)?,
+                        Err(e) => {
+                            if cfg!(dev) {
+                                println!("{e:?}");
+                            }
+
+                            return None;
+                        }
+                    };
+
+                    // $R с тем же суффиксом хранит само содержимое
+                    let payload = user.path().join(format!("$R{}", suffix));
+
+                    Some(RecycleBinItem {
+                        sid: sid.clone(),
+                        user: String::new(),
+                        index_path: file.path().to_string_lossy().to_string(),
+                        payload_path: payload.to_string_lossy().to_string(),
+                        original_path: index.path,
+                        size: index.size,
+                        deleted: index.deleted,
+                        version: index.version,
+                        payload_exists: payload.exists(),
+                    })
+                })
+                .collect()
+        })
+        .collect()
+}
+
+// версия 1 (vista - 8.1): путь в буфере на 260 символов,
+// версия 2 (windows 10+): длина пути в символах и сам путь
+pub fn parse_index(data: &[u8]) -> Option<RecycleBinIndex> {
+    if data.len() < HEADER_SIZE {
+        return None;
+    }
+
+    let version = u64::from_le_bytes(data[0..8].try_into().ok()?);
+    let size = u64::from_le_bytes(data[8..16].try_into().ok()?);
+    let deleted = i64::from_le_bytes(data[16..24].try_into().ok()?);
+
+    let path_bytes = match version {
+        1 => data.get(HEADER_SIZE..(HEADER_SIZE + V1_PATH_SIZE).min(data.len()))?,
+        2 => {
+            let length = u32::from_le_bytes(data.get(24..28)?.try_into().ok()?) as usize;
+            data.get(28..(28 + length * 2).min(data.len()))?
+        }
+        _ => return None,
+    };
+
+    let path = utf16_bytes_to_string(path_bytes);
+    let path = path.split('\0').next().unwrap_or_default().to_string();
+
+    Some(RecycleBinIndex {
+        version,
+        size,
+        deleted,
+        path,
+    })
+}
@@ -0,0 +1,589 @@
+use std::fs;
+
+use crate::utils::utf16_bytes_to_string;
+
+const HIVE_SIGNATURE: &[u8] = b"regf";
+const BASE_BLOCK_SIZE: usize = 4096;
+
+// журналы транзакций нового формата (Windows 8.1+), записи идут после базового блока
+// https://github.com/msuhanov/regf/blob/master/Windows%20registry%20file%20format%20specification.md#new-format
+const LOG_EXTENSIONS: [&str; 2] = ["LOG1", "LOG2"];
+const LOG_ENTRY_SIGNATURE: &[u8] = b"HvLE";
+const LOG_ENTRIES_OFFSET: usize = 512;
+const LOG_ENTRY_HEADER_SIZE: usize = 40;
+
+const KEY_COMP_NAME: u16 = 0x0020;
+const VALUE_COMP_NAME: u16 = 0x0001;
+
+// данные значения размером до 4 байт хранятся прямо в поле смещения
+const DATA_IN_OFFSET: u32 = 0x80000000;
+const BIG_DATA_SEGMENT_SIZE: usize = 16344;
+const MAX_INDEX_DEPTH: usize = 1;
+
+pub const REG_SZ: u32 = 1;
+pub const REG_EXPAND_SZ: u32 = 2;
+pub const REG_BINARY: u32 = 3;
+pub const REG_DWORD: u32 = 4;
+pub const REG_MULTI_SZ: u32 = 7;
+pub const REG_QWORD: u32 = 11;
+
+// https://github.com/msuhanov/regf/blob/master/Windows%20registry%20file%20format%20specification.md
+pub struct Hive {
+    data: Vec<u8>,
+    // куст не был сброшен на диск, а журналы применить не удалось
+    pub dirty: bool,
+}
+
+// страницы, записанные одной транзакцией
+struct LogEntry<'a> {
+    sequence: u32,
+    bins_size: usize,
+    pages: Vec<(usize, &'a [u8])>,
+}
+
+#[derive(Clone)]
+pub struct HiveKey<'a> {
+    hive: &'a Hive,
+    offset: usize,
+    pub name: String,
+    pub last_write: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct HiveValue {
+    pub name: String,
+    pub value_type: u32,
+    pub data: Vec<u8>,
+}
+
+impl Hive {
+    pub fn open(path: &str) -> Option<Self> {
+        let mut hive = match fs::read(path) {
+            Ok(data) => Self::from_bytes(data)?,
+            Err(e) => {
+                if cfg!(dev) {
+                    println!("{e:?}");
+                }
+
+                return None;
+            }
+        };
+
+        if hive.dirty {
+            let logs: Vec<Vec<u8>> = LOG_EXTENSIONS
+                .iter()
+                .filter_map(|extension| fs::read(format!("{}.{}", path, extension)).ok())
+                .collect();
+
+            hive.replay(&logs);
+        }
+
+        Some(hive)
+    }
+
+    pub fn from_bytes(data: Vec<u8>) -> Option<Self> {
+        if !data.starts_with(HIVE_SIGNATURE) || data.len() < BASE_BLOCK_SIZE {
+            return None;
+        }
+
+        // последовательные номера расходятся, пока запись куста не завершена
+        let dirty = read_u32(&data, 4)? != read_u32(&data, 8)?;
+
+        Some(Self { data, dirty })
+    }
+
+    // применяем записи журналов по порядку, начиная с номера, на котором остановилась запись куста
+    pub fn replay(&mut self, logs: &[Vec<u8>]) -> bool {
+        let Some(mut sequence) = read_u32(&self.data, 8) else {
+            return false;
+        };
+
+        let mut entries: Vec<LogEntry> =
+            logs.iter().flat_map(|log| read_log_entries(log)).collect();
+        entries.sort_by_key(|entry| entry.sequence);
+
+        let mut applied = false;
+
+        for entry in entries {
+            if entry.sequence < sequence {
+                continue;
+            }
+
+            if entry.sequence != sequence {
+                break;
+            }
+
+            let size = BASE_BLOCK_SIZE + entry.bins_size;
+            if self.data.len() < size {
+                self.data.resize(size, 0);
+            }
+
+            for (offset, page) in entry.pages {
+                let start = BASE_BLOCK_SIZE + offset;
+
+                if let Some(target) = self.data.get_mut(start..start + page.len()) {
+                    target.copy_from_slice(page);
+                }
+            }
+
+            sequence = sequence.wrapping_add(1);
+            applied = true;
+        }
+
+        if applied {
+            self.dirty = false;
+        }
+
+        applied
+    }
+
+    pub fn root(&self) -> Option<HiveKey<'_>> {
+        self.key(read_u32(&self.data, 0x24)? as usize)
+    }
+
+    pub fn open_key(&self, path: &str) -> Option<HiveKey<'_>> {
+        self.root()?.open(path)
+    }
+
+    // смещения ячеек отсчитываются от начала первого блока данных, ячейка начинается с размера
+    fn cell(&self, offset: usize) -> Option<&[u8]> {
+        let start = BASE_BLOCK_SIZE + offset;
+        let size = read_u32(&self.data, start)? as i32;
+
+        self.data
+            .get(start + 4..start + size.unsigned_abs() as usize)
+    }
+
+    fn key(&self, offset: usize) -> Option<HiveKey<'_>> {
+        let cell = self.cell(offset)?;
+
+        if !cell.starts_with(b"nk") {
+            return None;
+        }
+
+        let flags = read_u16(cell, 2)?;
+        let name_size = read_u16(cell, 72)? as usize;
+        let name = cell.get(76..76 + name_size)?;
+
+        Some(HiveKey {
+            hive: self,
+            offset,
+            name: decode_name(name, flags & KEY_COMP_NAME != 0),
+            last_write: read_u64(cell, 4)? as i64,
+        })
+    }
+
+    fn subkey_offsets(&self, list_offset: usize, offsets: &mut Vec<usize>, depth: usize) {
+        // корень ссылается только на обычные списки, глубже идут лишь испорченные файлы
+        if depth > MAX_INDEX_DEPTH {
+            return;
+        }
+
+        let Some(cell) = self.cell(list_offset) else {
+            return;
+        };
+
+        let count = read_u16(cell, 2).unwrap_or(0) as usize;
+
+        match cell.get(..2) {
+            Some(b"lf") | Some(b"lh") => {
+                for i in 0..count {
+                    if let Some(offset) = read_u32(cell, 4 + i * 8) {
+                        offsets.push(offset as usize);
+                    }
+                }
+            }
+            Some(b"li") => {
+                for i in 0..count {
+                    if let Some(offset) = read_u32(cell, 4 + i * 4) {
+                        offsets.push(offset as usize);
+                    }
+                }
+            }
+            // индексный корень ссылается на другие списки
+            Some(b"ri") => {
+                for i in 0..count {
+                    if let Some(offset) = read_u32(cell, 4 + i * 4) {
+                        self.subkey_offsets(offset as usize, offsets, depth + 1);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn value(&self, offset: usize) -> Option<HiveValue> {
+        let cell = self.cell(offset)?;
+
+        if !cell.starts_with(b"vk") {
+            return None;
+        }
+
+        let name_size = read_u16(cell, 2)? as usize;
+        let data_size = read_u32(cell, 4)?;
+        let data_offset = read_u32(cell, 8)?;
+        let value_type = read_u32(cell, 12)?;
+        let flags = read_u16(cell, 16)?;
+
+        let name = decode_name(cell.get(20..20 + name_size)?, flags & VALUE_COMP_NAME != 0);
+
+        let data = if data_size & DATA_IN_OFFSET != 0 {
+            let size = (data_size & !DATA_IN_OFFSET).min(4) as usize;
+            cell.get(8..8 + size)?.to_vec()
+        } else {
+            self.value_data(data_offset as usize, data_size as usize)?
+        };
+
+        Some(HiveValue {
+            name,
+            value_type,
+            data,
+        })
+    }
+
+    fn value_data(&self, offset: usize, size: usize) -> Option<Vec<u8>> {
+        let cell = self.cell(offset)?;
+
+        // большие значения разбиты на сегменты
+        if size > BIG_DATA_SEGMENT_SIZE && cell.starts_with(b"db") {
+            let count = read_u16(cell, 2)? as usize;
+            let list = self.cell(read_u32(cell, 4)? as usize)?;
+            let mut data = Vec::with_capacity(size);
+
+            for i in 0..count {
+                let segment = self.cell(read_u32(list, i * 4)? as usize)?;
+                let remaining = size - data.len();
+                data.extend_from_slice(&segment[..segment.len().min(remaining)]);
+            }
+
+            return Some(data);
+        }
+
+        Some(cell.get(..size.min(cell.len()))?.to_vec())
+    }
+}
+
+impl<'a> HiveKey<'a> {
+    pub fn keys(&self) -> Vec<HiveKey<'a>> {
+        let Some(cell) = self.hive.cell(self.offset) else {
+            return vec![];
+        };
+
+        let count = read_u32(cell, 20).unwrap_or(0);
+        let Some(list_offset) = read_u32(cell, 28) else {
+            return vec![];
+        };
+
+        if count == 0 || list_offset == u32::MAX {
+            return vec![];
+        }
+
+        let mut offsets = vec![];
+        self.hive
+            .subkey_offsets(list_offset as usize, &mut offsets, 0);
+
+        offsets
+            .into_iter()
+            .filter_map(|offset| self.hive.key(offset))
+            .collect()
+    }
+
+    pub fn key(&self, name: &str) -> Option<HiveKey<'a>> {
+        self.keys()
+            .into_iter()
+            .find(|key| key.name.eq_ignore_ascii_case(name))
+    }
+
+    pub fn open(&self, path: &str) -> Option<HiveKey<'a>> {
+        let mut key = self.clone();
+
+        for name in path.split('\\').filter(|name| !name.is_empty()) {
+            key = key.key(name)?;
+        }
+
+        Some(key)
+    }
+
+    pub fn values(&self) -> Vec<HiveValue> {
+        let Some(cell) = self.hive.cell(self.offset) else {
+            return vec![];
+        };
+
+        let count = read_u32(cell, 36).unwrap_or(0) as usize;
+        let Some(list) = read_u32(cell, 40).and_then(|offset| self.hive.cell(offset as usize))
+        else {
+            return vec![];
+        };
+
+        (0..count)
+            .filter_map(|i| read_u32(list, i * 4))
+            .filter_map(|offset| self.hive.value(offset as usize))
+            .collect()
+    }
+
+    pub fn value(&self, name: &str) -> Option<HiveValue> {
+        self.values()
+            .into_iter()
+            .find(|value| value.name.eq_ignore_ascii_case(name))
+    }
+}
+
+impl HiveValue {
+    pub fn as_string(&self) -> Option<String> {
+        match self.value_type {
+            REG_SZ | REG_EXPAND_SZ => Some(utf16_bytes_to_string(&self.data)),
+            _ => None,
+        }
+    }
+
+    pub fn as_multi_string(&self) -> Vec<String> {
+        utf16_bytes_to_string(&self.data)
+            .split('\0')
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string())
+            .collect()
+    }
+
+    pub fn as_u32(&self) -> Option<u32> {
+        read_u32(&self.data, 0)
+    }
+
+    pub fn as_u64(&self) -> Option<u64> {
+        read_u64(&self.data, 0)
+    }
+}
+
+fn decode_name(name: &[u8], compressed: bool) -> String {
+    match compressed {
+        // сжатые имена хранятся в latin-1
+        true => name.iter().map(|b| *b as char).collect(),
+        false => utf16_bytes_to_string(name),
+    }
+}
+
+// хэши записей не сверяем, вместо этого обрываемся на первой неполной записи
+fn read_log_entries(log: &[u8]) -> Vec<LogEntry<'_>> {
+    let mut entries = vec![];
+    let mut offset = LOG_ENTRIES_OFFSET;
+
+    while let Some((size, entry)) = read_log_entry(log, offset) {
+        offset += size;
+        entries.push(entry);
+    }
+
+    entries
+}
+
+fn read_log_entry(log: &[u8], offset: usize) -> Option<(usize, LogEntry<'_>)> {
+    let header = log.get(offset..offset + LOG_ENTRY_HEADER_SIZE)?;
+
+    if !header.starts_with(LOG_ENTRY_SIGNATURE) {
+        return None;
+    }
+
+    let size = read_u32(header, 4)? as usize;
+    if size < LOG_ENTRY_HEADER_SIZE || !size.is_multiple_of(512) {
+        return None;
+    }
+
+    let entry = log.get(offset..offset + size)?;
+    let count = read_u32(entry, 20)? as usize;
+
+    // за заголовком идут ссылки на страницы (смещение, размер), за ними сами страницы
+    let mut data_offset = LOG_ENTRY_HEADER_SIZE + count.checked_mul(8)?;
+    let mut pages = vec![];
+
+    for i in 0..count {
+        let reference = LOG_ENTRY_HEADER_SIZE + i * 8;
+        let page_offset = read_u32(entry, reference)? as usize;
+        let page_size = read_u32(entry, reference + 4)? as usize;
+
+        pages.push((
+            page_offset,
+            entry.get(data_offset..data_offset + page_size)?,
+        ));
+        data_offset += page_size;
+    }
+
+    Some((
+        size,
+        LogEntry {
+            sequence: read_u32(entry, 12)?,
+            bins_size: read_u32(entry, 16)? as usize,
+            pages,
+        },
+    ))
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(
+        data.get(offset..offset + 2)?.try_into().ok()?,
+    ))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(
+        data.get(offset..offset + 4)?.try_into().ok()?,
+    ))
+}
+
+fn read_u64(data: &[u8], offset: usize) -> Option<u64> {
+    Some(u64::from_le_bytes(
+        data.get(offset..offset + 8)?.try_into().ok()?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BINS_SIZE: usize = 4096;
+
+    struct Builder {
+        bins: Vec<u8>,
+    }
+
+    impl Builder {
+        fn new() -> Self {
+            let mut bins = b"hbin".to_vec();
+            bins.resize(32, 0);
+            Self { bins }
+        }
+
+        // занятая ячейка хранит отрицательный размер вместе с самим полем размера
+        fn cell(&mut self, content: &[u8]) -> u32 {
+            let offset = self.bins.len() as u32;
+            let size = (content.len() + 4).next_multiple_of(8);
+
+            self.bins.extend_from_slice(&(-(size as i32)).to_le_bytes());
+            self.bins.extend_from_slice(content);
+            self.bins.resize(offset as usize + size, 0);
+
+            offset
+        }
+
+        fn key(&mut self, name: &str, subkeys: (u32, u32), values: (u32, u32)) -> u32 {
+            let mut cell = vec![0; 76];
+            cell[..2].copy_from_slice(b"nk");
+            cell[2..4].copy_from_slice(&KEY_COMP_NAME.to_le_bytes());
+            cell[4..12].copy_from_slice(&133_000_000_000_000_000u64.to_le_bytes());
+            cell[20..24].copy_from_slice(&subkeys.0.to_le_bytes());
+            cell[28..32].copy_from_slice(&subkeys.1.to_le_bytes());
+            cell[36..40].copy_from_slice(&values.0.to_le_bytes());
+            cell[40..44].copy_from_slice(&values.1.to_le_bytes());
+            cell[72..74].copy_from_slice(&(name.len() as u16).to_le_bytes());
+            cell.extend_from_slice(name.as_bytes());
+
+            self.cell(&cell)
+        }
+
+        fn dword(&mut self, name: &str, value: u32) -> u32 {
+            let mut cell = vec![0; 20];
+            cell[..2].copy_from_slice(b"vk");
+            cell[2..4].copy_from_slice(&(name.len() as u16).to_le_bytes());
+            cell[4..8].copy_from_slice(&(4 | DATA_IN_OFFSET).to_le_bytes());
+            cell[8..12].copy_from_slice(&value.to_le_bytes());
+            cell[12..16].copy_from_slice(&REG_DWORD.to_le_bytes());
+            cell[16..18].copy_from_slice(&VALUE_COMP_NAME.to_le_bytes());
+            cell.extend_from_slice(name.as_bytes());
+
+            self.cell(&cell)
+        }
+
+        fn list(&mut self, signature: &[u8], offsets: &[u32], stride: usize) -> u32 {
+            let mut cell = signature.to_vec();
+            cell.extend_from_slice(&(offsets.len() as u16).to_le_bytes());
+
+            for offset in offsets {
+                cell.extend_from_slice(&offset.to_le_bytes());
+                cell.resize(cell.len() + stride - 4, 0);
+            }
+
+            self.cell(&cell)
+        }
+
+        fn build(mut self, root: u32, sequences: (u32, u32)) -> Vec<u8> {
+            let mut data = vec![0; BASE_BLOCK_SIZE];
+            data[..4].copy_from_slice(HIVE_SIGNATURE);
+            data[4..8].copy_from_slice(&sequences.0.to_le_bytes());
+            data[8..12].copy_from_slice(&sequences.1.to_le_bytes());
+            data[0x24..0x28].copy_from_slice(&root.to_le_bytes());
+
+            self.bins.resize(BINS_SIZE, 0);
+            data.extend(self.bins);
+            data
+        }
+    }
+
+    // корень\Objects со значением Type
+    fn hive(value: u32, sequences: (u32, u32)) -> Vec<u8> {
+        let mut b = Builder::new();
+
+        let value = b.dword("Type", value);
+        let values = b.cell(&value.to_le_bytes());
+        let objects = b.key("Objects", (0, u32::MAX), (1, values));
+        let list = b.list(b"lh", &[objects], 8);
+        let root = b.key("ROOT", (1, list), (0, u32::MAX));
+
+        b.build(root, sequences)
+    }
+
+    fn type_value(hive: &Hive) -> Option<u32> {
+        hive.open_key("objects")?.value("type")?.as_u32()
+    }
+
+    #[test]
+    fn reads_keys_and_values() {
+        let hive = Hive::from_bytes(hive(0x10200003, (1, 1))).unwrap();
+
+        assert!(!hive.dirty);
+        assert_eq!(hive.root().unwrap().name, "ROOT");
+        assert_eq!(type_value(&hive), Some(0x10200003));
+        assert_eq!(
+            hive.open_key("Objects").unwrap().last_write,
+            133_000_000_000_000_000
+        );
+    }
+
+    #[test]
+    fn stops_on_self_referencing_index_root() {
+        let mut b = Builder::new();
+
+        // ri, который указывает сам на себя
+        let offset = b.bins.len() as u32;
+        let list = b.list(b"ri", &[offset], 4);
+        assert_eq!(list, offset);
+
+        let root = b.key("ROOT", (1, list), (0, u32::MAX));
+        let hive = Hive::from_bytes(b.build(root, (1, 1))).unwrap();
+
+        assert!(hive.root().unwrap().keys().is_empty());
+    }
+
+    #[test]
+    fn replays_transaction_log() {
+        let mut hive = Hive::from_bytes(hive(1, (2, 1))).unwrap();
+        assert!(hive.dirty);
+
+        let page = &self::hive(2, (2, 2))[BASE_BLOCK_SIZE..];
+
+        let mut entry = LOG_ENTRY_SIGNATURE.to_vec();
+        entry.resize(LOG_ENTRY_HEADER_SIZE, 0);
+        entry[12..16].copy_from_slice(&1u32.to_le_bytes());
+        entry[16..20].copy_from_slice(&(BINS_SIZE as u32).to_le_bytes());
+        entry[20..24].copy_from_slice(&1u32.to_le_bytes());
+        entry.extend_from_slice(&0u32.to_le_bytes());
+        entry.extend_from_slice(&(page.len() as u32).to_le_bytes());
+        entry.extend_from_slice(page);
+        entry.resize(entry.len().next_multiple_of(512), 0);
+        let size = entry.len() as u32;
+        entry[4..8].copy_from_slice(&size.to_le_bytes());
+
+        let mut log = vec![0; LOG_ENTRIES_OFFSET];
+        log.extend(entry);
+
+        assert!(hive.replay(&[log]));
+        assert!(!hive.dirty);
+        assert_eq!(type_value(&hive), Some(2));
+    }
+}
@@ -0,0 +1,146 @@
+use std::{fs, path::Path};
+
+use regex::Regex;
+
+use super::remote_access::{close_last_session, parse_utc_time, read_sessions, RemoteSession};
+
+pub const ANYDESK_DIR: &str = "AnyDesk";
+pub const ANYDESK_SYSTEM_DIR: &str = "C:\\ProgramData\\AnyDesk";
+
+const TOOL: &str = "AnyDesk";
+const CONNECTION_TRACE_FILE: &str = "connection_trace.txt";
+
+const CONNECTION_TIME_FORMAT: &str = "%Y-%m-%d, %H:%M";
+const TRACE_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.f";
+
+const SESSION_REQUEST: &str = r"Incoming session request: (.*?)\s*\((\d+)\)";
+const SESSION_END_MARKERS: [&str; 2] = ["Session closed", "Session stopped"];
+
+pub fn read_anydesk_dir(user: &str, dir: &Path) -> Vec<RemoteSession> {
+    let Ok(files) = fs::read_dir(dir) else {
+        return vec![];
+    };
+
+    files
+        .filter_map(Result::ok)
+        .flat_map(|file| {
+            let name = file.file_name().to_string_lossy().to_lowercase();
+
+            // ad.trace у клиента и ad_svc.trace у службы, вместе с ротированными .old
+            if name.eq(CONNECTION_TRACE_FILE) {
+                read_sessions(user, &file.path(), parse_connection_trace)
+            } else if name.starts_with("ad") && name.contains(".trace") {
+                read_sessions(user, &file.path(), parse_anydesk_trace)
+            } else {
+                vec![]
+            }
+        })
+        .collect()
+}
+
+// Incoming    2024-01-01, 10:00    User    123456789    123456789
+pub fn parse_connection_trace(text: &str) -> Vec<RemoteSession> {
+    text.lines()
+        .filter_map(|line| {
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+
+            if tokens.len() < 5 || !tokens[0].eq_ignore_ascii_case("incoming") {
+                return None;
+            }
+
+            let start = parse_utc_time(
+                &format!("{} {}", tokens[1], tokens[2]),
+                CONNECTION_TIME_FORMAT,
+            )?;
+
+            let mut session = RemoteSession::new(TOOL, start);
+            session.peer_id = tokens[tokens.len() - 1].to_string();
+            session.status = match tokens[3] {
+                "User" => "принято пользователем",
+                "Passwd" => "вход по паролю",
+                "Token" => "вход по токену",
+                "REJECTED" => "отклонено",
+                status => status,
+            }
+            .to_string();
+
+            Some(session)
+        })
+        .collect()
+}
+
+//    info 2024-01-01 10:00:00.000  back  1234  5678  app.backend_session - Incoming session request: DESKTOP (123456789)
+pub fn parse_anydesk_trace(text: &str) -> Vec<RemoteSession> {
+    let Ok(request) = Regex::new(SESSION_REQUEST) else {
+        return vec![];
+    };
+
+    let mut sessions = vec![];
+
+    for line in text.lines() {
+        let mut tokens = line.split_whitespace().skip(1);
+
+        let (Some(date), Some(time)) = (tokens.next(), tokens.next()) else {
+            continue;
+        };
+
+        let Some(timestamp) = parse_utc_time(&format!("{} {}", date, time), TRACE_TIME_FORMAT)
+        else {
+            continue;
+        };
+
+        if let Some(captures) = request.captures(line) {
+            let mut session = RemoteSession::new(TOOL, timestamp);
+            session.peer_name = captures[1].to_string();
+            session.peer_id = captures[2].to_string();
+            session.status = String::from("запрос входящей сессии");
+
+            sessions.push(session);
+        } else if SESSION_END_MARKERS
+            .iter()
+            .any(|marker| line.contains(marker))
+        {
+            close_last_session(&mut sessions, timestamp);
+        }
+    }
+
+    sessions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_connection_trace() {
+        let sessions = parse_connection_trace(
+            "Incoming    2024-01-01, 10:00    User      123456789    987654321\n\
+             Outgoing    2024-01-01, 11:00    User      123456789    987654321\n\
+             Incoming    2024-01-01, 12:00    REJECTED  123456789    555555555\n",
+        );
+
+        assert_eq!(sessions.len(), 2);
+        assert_eq!(sessions[0].start, 1704103200);
+        assert_eq!(sessions[0].peer_id, "987654321");
+        assert_eq!(sessions[0].status, "принято пользователем");
+        assert_eq!(sessions[1].status, "отклонено");
+        assert_eq!(sessions[1].start - sessions[0].start, 2 * 3600);
+    }
+
+    #[test]
+    fn parses_anydesk_trace() {
+        let sessions = parse_anydesk_trace(
+            "   info 2024-01-01 10:00:00.000  back  1234  5678  app.backend_session - Incoming session request: DESKTOP-1 (123456789)\n\
+             \x20  info 2024-01-01 10:15:30.500  back  1234  5678  app.session - Session closed by remote.\n\
+             \x20  info 2024-01-01 11:00:00.000  back  1234  5678  app.backend_session - Incoming session request: LAPTOP (987654321)\n",
+        );
+
+        assert_eq!(sessions.len(), 2);
+        assert_eq!(sessions[0].start, 1704103200);
+        assert_eq!(sessions[0].peer_name, "DESKTOP-1");
+        assert_eq!(sessions[0].peer_id, "123456789");
+        assert_eq!(sessions[0].duration(), Some(15 * 60 + 30));
+        assert_eq!(sessions[1].peer_id, "987654321");
+        assert_eq!(sessions[1].end, 0);
+    }
+}
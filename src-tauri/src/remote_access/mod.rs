@@ -0,0 +1,4 @@
+pub mod anydesk;
+pub mod remote_access;
+pub mod rustdesk;
+pub mod teamviewer;
@@ -0,0 +1,169 @@
+use std::{fs, path::Path};
+
+use chrono::{Local, NaiveDateTime, TimeZone};
+use serde::{Deserialize, Serialize};
+
+use crate::{profiles::profiles::collect_user_profiles, utils::text_bytes_to_string};
+
+use super::{
+    anydesk::{read_anydesk_dir, ANYDESK_DIR, ANYDESK_SYSTEM_DIR},
+    rustdesk::{read_rustdesk_dir, RUSTDESK_DIR, RUSTDESK_SERVICE_DIR},
+    teamviewer::{local_client_id, read_teamviewer_dir, TEAMVIEWER_DIR, TEAMVIEWER_SYSTEM_DIRS},
+};
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RemoteSession {
+    pub user: String,
+    pub tool: &'static str,
+    pub peer_id: String,
+    pub peer_name: String,
+    pub start: i64,
+    pub end: i64,
+    pub status: String,
+    pub source: String,
+}
+
+impl RemoteSession {
+    pub fn new(tool: &'static str, start: i64) -> Self {
+        Self {
+            user: String::new(),
+            tool,
+            peer_id: String::new(),
+            peer_name: String::new(),
+            start,
+            end: 0,
+            status: String::new(),
+            source: String::new(),
+        }
+    }
+
+    pub fn duration(&self) -> Option<i64> {
+        (self.end > 0 && self.end >= self.start).then(|| self.end - self.start)
+    }
+}
+
+// журналы служб общие для системы, в профилях лежат журналы пользователя
+pub fn collect_remote_sessions() -> Vec<RemoteSession> {
+    let mut sessions = vec![];
+    let client_id = local_client_id();
+
+    sessions.extend(read_anydesk_dir("", Path::new(ANYDESK_SYSTEM_DIR)));
+    sessions.extend(read_rustdesk_dir("", Path::new(RUSTDESK_SERVICE_DIR)));
+
+    for dir in TEAMVIEWER_SYSTEM_DIRS {
+        sessions.extend(read_teamviewer_dir(
+            "",
+            Path::new(dir),
+            client_id.as_deref(),
+        ));
+    }
+
+    for profile in collect_user_profiles() {
+        let app_data = profile.app_data();
+
+        sessions.extend(read_anydesk_dir(&profile.name, &app_data.join(ANYDESK_DIR)));
+        sessions.extend(read_teamviewer_dir(
+            &profile.name,
+            &app_data.join(TEAMVIEWER_DIR),
+            client_id.as_deref(),
+        ));
+        sessions.extend(read_rustdesk_dir(
+            &profile.name,
+            &app_data.join(RUSTDESK_DIR),
+        ));
+    }
+
+    sessions
+}
+
+pub fn read_sessions(
+    user: &str,
+    path: &Path,
+    parse: fn(&str) -> Vec<RemoteSession>,
+) -> Vec<RemoteSession> {
+    let data = match fs::read(path) {
+        Ok(data) => data,
+        Err(e) => {
+            if cfg!(dev) {
+                println!("{e:?}");
+            }
+
+            return vec![];
+        }
+    };
+
+    parse(&text_bytes_to_string(&data))
+        .into_iter()
+        .map(|mut session| {
+            session.user = user.to_string();
+            session.source = path.to_string_lossy().to_string();
+            session
+        })
+        .collect()
+}
+
+// журналы пишут окончание без идентификатора, закрываем последнюю открытую сессию
+pub fn close_last_session(sessions: &mut [RemoteSession], end: i64) {
+    if let Some(session) = sessions.iter_mut().rev().find(|session| session.end == 0) {
+        session.end = end;
+    }
+}
+
+// журналы teamviewer пишут время в часовом поясе компьютера
+pub fn parse_time(time: &str, format: &str) -> Option<i64> {
+    let time = NaiveDateTime::parse_from_str(time.trim(), format).ok()?;
+
+    Local
+        .from_local_datetime(&time)
+        .earliest()
+        .map(|time| time.timestamp())
+}
+
+// журналы anydesk пишут время в utc
+pub fn parse_utc_time(time: &str, format: &str) -> Option<i64> {
+    NaiveDateTime::parse_from_str(time.trim(), format)
+        .ok()
+        .map(|time| time.and_utc().timestamp())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_local_time() {
+        let expected = Local
+            .with_ymd_and_hms(2024, 1, 1, 10, 0, 0)
+            .unwrap()
+            .timestamp();
+
+        assert_eq!(
+            parse_time("2024-01-01 10:00:00", "%Y-%m-%d %H:%M:%S"),
+            Some(expected)
+        );
+        assert_eq!(parse_time("01.01.2024", "%Y-%m-%d %H:%M:%S"), None);
+    }
+
+    #[test]
+    fn parses_utc_time() {
+        assert_eq!(
+            parse_utc_time("2024-01-01 10:00:00", "%Y-%m-%d %H:%M:%S"),
+            Some(1704103200)
+        );
+    }
+
+    #[test]
+    fn closes_last_open_session() {
+        let mut sessions = vec![
+            RemoteSession::new("AnyDesk", 100),
+            RemoteSession::new("AnyDesk", 200),
+        ];
+        sessions[1].end = 250;
+
+        close_last_session(&mut sessions, 300);
+
+        assert_eq!(sessions[0].duration(), Some(200));
+        assert_eq!(sessions[1].duration(), Some(50));
+        assert_eq!(RemoteSession::new("AnyDesk", 100).duration(), None);
+    }
+}
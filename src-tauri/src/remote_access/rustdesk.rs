@@ -0,0 +1,99 @@
+use std::path::Path;
+
+use chrono::DateTime;
+use jwalk::WalkDir;
+use regex::Regex;
+
+use super::remote_access::{close_last_session, read_sessions, RemoteSession};
+
+pub const RUSTDESK_DIR: &str = "RustDesk\\log";
+// журналы службы, которая принимает входящие подключения
+pub const RUSTDESK_SERVICE_DIR: &str =
+    "C:\\Windows\\ServiceProfiles\\LocalService\\AppData\\Roaming\\RustDesk\\log";
+
+const TOOL: &str = "RustDesk";
+const LOG_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.f %:z";
+
+const CONNECTION_OPENED: &str = "Connection opened from ";
+const CONNECTION_CLOSED: &str = "Connection closed";
+const PEER_ID: &str = r#"my_id: "([^"]*)""#;
+const PEER_NAME: &str = r#"my_name: "([^"]*)""#;
+
+pub fn read_rustdesk_dir(user: &str, dir: &Path) -> Vec<RemoteSession> {
+    WalkDir::new(dir)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type.is_file())
+        .filter(|entry| {
+            entry
+                .file_name
+                .to_string_lossy()
+                .to_lowercase()
+                .ends_with(".log")
+        })
+        .flat_map(|entry| read_sessions(user, &entry.path(), parse_rustdesk_log))
+        .collect()
+}
+
+// [2024-01-01 10:00:00.123456 +03:00] INFO [src\server\connection.rs:100] Connection opened from 1.2.3.4:5678.
+pub fn parse_rustdesk_log(text: &str) -> Vec<RemoteSession> {
+    let (Ok(peer_id), Ok(peer_name)) = (Regex::new(PEER_ID), Regex::new(PEER_NAME)) else {
+        return vec![];
+    };
+
+    let mut sessions: Vec<RemoteSession> = vec![];
+
+    for line in text.lines() {
+        let Some(timestamp) = line
+            .strip_prefix('[')
+            .and_then(|line| line.split_once(']'))
+            .and_then(|(time, _)| DateTime::parse_from_str(time, LOG_TIME_FORMAT).ok())
+            .map(|time| time.timestamp())
+        else {
+            continue;
+        };
+
+        if let Some((_, address)) = line.split_once(CONNECTION_OPENED) {
+            let mut session = RemoteSession::new(TOOL, timestamp);
+            session.peer_name = address.trim().trim_end_matches('.').to_string();
+            session.status = String::from("входящее подключение");
+
+            sessions.push(session);
+        } else if line.contains(CONNECTION_CLOSED) {
+            close_last_session(&mut sessions, timestamp);
+        } else if let Some(captures) = peer_id.captures(line) {
+            // запрос входа приходит после открытия соединения и содержит id и имя удаленной стороны
+            let Some(session) = sessions.iter_mut().rev().find(|session| session.end == 0) else {
+                continue;
+            };
+
+            session.peer_id = captures[1].to_string();
+
+            if let Some(name) = peer_name.captures(line) {
+                session.peer_name = format!("{} ({})", &name[1], session.peer_name);
+            }
+        }
+    }
+
+    sessions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_rustdesk_log() {
+        let sessions = parse_rustdesk_log(
+            "[2024-01-01 10:00:00.123456 +03:00] INFO [src\\server\\connection.rs:100] Connection opened from 1.2.3.4:5678.\n\
+             [2024-01-01 10:00:01.000000 +03:00] INFO [src\\server\\connection.rs:200] #1 Connection #1 login request: LoginRequest { username: \"\", my_id: \"123456789\", my_name: \"DESKTOP\" }\n\
+             [2024-01-01 10:05:00.000000 +03:00] INFO [src\\server\\connection.rs:300] #1 Connection closed: Reset by the peer\n",
+        );
+
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].start, 1704092400);
+        assert_eq!(sessions[0].peer_id, "123456789");
+        assert_eq!(sessions[0].peer_name, "DESKTOP (1.2.3.4:5678)");
+        assert_eq!(sessions[0].end, 1704092700);
+    }
+}
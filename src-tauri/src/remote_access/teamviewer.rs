@@ -0,0 +1,161 @@
+use std::{fs, path::Path};
+
+use regex::Regex;
+use windows_registry::LOCAL_MACHINE;
+
+use super::remote_access::{parse_time, read_sessions, RemoteSession};
+
+pub const TEAMVIEWER_DIR: &str = "TeamViewer";
+pub const TEAMVIEWER_SYSTEM_DIRS: [&str; 2] = [
+    "C:\\Program Files\\TeamViewer",
+    "C:\\Program Files (x86)\\TeamViewer",
+];
+
+const TEAMVIEWER_KEYS: [&str; 2] = ["SOFTWARE\\TeamViewer", "SOFTWARE\\WOW6432Node\\TeamViewer"];
+
+const TOOL: &str = "TeamViewer";
+const INCOMING_FILE: &str = "connections_incoming.txt";
+
+const CONNECTION_TIME_FORMAT: &str = "%d-%m-%Y %H:%M:%S";
+const LOG_TIME_FORMAT: &str = "%Y/%m/%d %H:%M:%S%.f";
+
+const PARTICIPANT: &str = r"(Add|Remove)Participant: \[(\d+),[^\]]*\](?:, type=\d+, name=([^,]*))?";
+
+pub fn read_teamviewer_dir(user: &str, dir: &Path, client_id: Option<&str>) -> Vec<RemoteSession> {
+    let Ok(files) = fs::read_dir(dir) else {
+        return vec![];
+    };
+
+    files
+        .filter_map(Result::ok)
+        .flat_map(|file| {
+            let name = file.file_name().to_string_lossy().to_lowercase();
+
+            if name.eq(INCOMING_FILE) {
+                read_sessions(user, &file.path(), parse_incoming_connections)
+            } else if name.starts_with("teamviewer") && name.contains("_logfile") {
+                // в журнале участником сессии числится и сам компьютер
+                read_sessions(user, &file.path(), parse_teamviewer_log)
+                    .into_iter()
+                    .filter(|session| Some(session.peer_id.as_str()).ne(&client_id))
+                    .collect()
+            } else {
+                vec![]
+            }
+        })
+        .collect()
+}
+
+// 123456789	DESKTOP	01-01-2024 10:00:00	01-01-2024 10:30:00	user	RemoteControl	{guid}
+pub fn parse_incoming_connections(text: &str) -> Vec<RemoteSession> {
+    text.lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split('\t').map(|field| field.trim()).collect();
+
+            if fields.len() < 4 {
+                return None;
+            }
+
+            let mut session =
+                RemoteSession::new(TOOL, parse_time(fields[2], CONNECTION_TIME_FORMAT)?);
+            session.peer_id = fields[0].to_string();
+            session.peer_name = fields[1].to_string();
+            session.end = parse_time(fields[3], CONNECTION_TIME_FORMAT).unwrap_or(0);
+            session.status = fields.get(5).unwrap_or(&"").to_string();
+
+            Some(session)
+        })
+        .collect()
+}
+
+// 2024/01/01 10:00:00.000  1234  5678 S0   CPersistentParticipantManager::AddParticipant: [123456789,-1234], type=3, name=DESKTOP
+pub fn parse_teamviewer_log(text: &str) -> Vec<RemoteSession> {
+    let Ok(participant) = Regex::new(PARTICIPANT) else {
+        return vec![];
+    };
+
+    let mut sessions: Vec<RemoteSession> = vec![];
+
+    for line in text.lines() {
+        let Some(captures) = participant.captures(line) else {
+            continue;
+        };
+
+        let mut tokens = line.split_whitespace();
+
+        let (Some(date), Some(time)) = (tokens.next(), tokens.next()) else {
+            continue;
+        };
+
+        let Some(timestamp) = parse_time(&format!("{} {}", date, time), LOG_TIME_FORMAT) else {
+            continue;
+        };
+
+        let peer_id = &captures[2];
+
+        if &captures[1] == "Add" {
+            let mut session = RemoteSession::new(TOOL, timestamp);
+            session.peer_id = peer_id.to_string();
+            session.peer_name = captures
+                .get(3)
+                .map(|name| name.as_str().trim().to_string())
+                .unwrap_or_default();
+            session.status = String::from("участник сессии");
+
+            sessions.push(session);
+        } else if let Some(session) = sessions
+            .iter_mut()
+            .rev()
+            .find(|session| session.peer_id.eq(peer_id) && session.end == 0)
+        {
+            session.end = timestamp;
+        }
+    }
+
+    sessions
+}
+
+pub fn local_client_id() -> Option<String> {
+    TEAMVIEWER_KEYS.iter().find_map(|path| {
+        LOCAL_MACHINE
+            .open(path)
+            .ok()?
+            .get_u32("ClientID")
+            .ok()
+            .map(|id| id.to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_incoming_connections() {
+        let sessions = parse_incoming_connections(
+            "123456789\tDESKTOP\t01-01-2024 10:00:00\t01-01-2024 10:30:00\tuser\tRemoteControl\t{guid}\n\
+             broken\tline\n",
+        );
+
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].peer_id, "123456789");
+        assert_eq!(sessions[0].peer_name, "DESKTOP");
+        assert_eq!(sessions[0].status, "RemoteControl");
+        assert_eq!(sessions[0].duration(), Some(30 * 60));
+    }
+
+    #[test]
+    fn parses_teamviewer_log() {
+        let sessions = parse_teamviewer_log(
+            "2024/01/01 10:00:00.000  1234  5678 S0   CPersistentParticipantManager::AddParticipant: [111111111,-1234], type=3, name=HOME\n\
+             2024/01/01 10:00:01.000  1234  5678 S0   CPersistentParticipantManager::AddParticipant: [123456789,-5678], type=6, name=DESKTOP\n\
+             2024/01/01 10:20:00.000  1234  5678 S0   CPersistentParticipantManager::RemoveParticipant: [123456789,-5678]\n",
+        );
+
+        assert_eq!(sessions.len(), 2);
+        assert_eq!(sessions[1].peer_id, "123456789");
+        assert_eq!(sessions[1].peer_name, "DESKTOP");
+        assert_eq!(sessions[1].duration(), Some(20 * 60 - 1));
+        assert_eq!(sessions[0].end, 0);
+    }
+}
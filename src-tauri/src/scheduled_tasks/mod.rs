@@ -0,0 +1,2 @@
+pub mod scheduled_tasks;
+pub mod task_xml;
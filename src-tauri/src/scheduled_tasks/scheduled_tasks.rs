@@ -0,0 +1,192 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    io::Read,
+};
+
+use jwalk::WalkDir;
+use serde::{Deserialize, Serialize};
+use windows_registry::{Key, LOCAL_MACHINE};
+
+use crate::{
+    mini_dat::registry_md::bytes_to_vec_u8,
+    utils::{text_bytes_to_string, utf16_bytes_to_string},
+};
+
+use super::task_xml::{parse_task_xml, TaskXml};
+
+pub const TASKS_DIR: &str = "C:\\Windows\\System32\\Tasks";
+const TASK_CACHE_KEY: &str = "SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion\\Schedule\\TaskCache";
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ScheduledTask {
+    pub path: String,
+    pub xml: Option<TaskXml>,
+    pub registry: bool,
+    // задача осталась в TaskCache\Tasks, но удалена из Tree и не видна в планировщике
+    pub tree: bool,
+    // без дескриптора безопасности в Tree задача не видна в планировщике
+    pub security_descriptor: bool,
+    pub registry_actions: String,
+}
+
+// запись задачи в TaskCache\Tree и ее описание в TaskCache\Tasks
+struct CachedTask {
+    tree: bool,
+    security_descriptor: bool,
+    actions: String,
+}
+
+pub fn collect_scheduled_tasks() -> Vec<ScheduledTask> {
+    let mut xml_tasks = read_task_files(TASKS_DIR);
+    let cached_tasks = read_task_cache();
+
+    let mut response: Vec<ScheduledTask> = cached_tasks
+        .into_iter()
+        .map(|(path, cached)| ScheduledTask {
+            xml: xml_tasks.remove(&path.to_lowercase()).map(|(_, xml)| xml),
+            path,
+            registry: true,
+            tree: cached.tree,
+            security_descriptor: cached.security_descriptor,
+            registry_actions: cached.actions,
+        })
+        .collect();
+
+    // оставшиеся файлы не имеют записи в реестре
+    response.extend(xml_tasks.into_values().map(|(path, xml)| ScheduledTask {
+        path,
+        xml: Some(xml),
+        registry: false,
+        tree: false,
+        security_descriptor: false,
+        registry_actions: String::new(),
+    }));
+
+    response.sort_by(|a, b| a.path.cmp(&b.path));
+    response
+}
+
+// ключ - путь задачи в нижнем регистре, значение - исходный путь и разобранный xml
+fn read_task_files(dir: &str) -> HashMap<String, (String, TaskXml)> {
+    WalkDir::new(dir)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type.is_file())
+        .filter_map(|entry| {
+            let path = entry.path();
+            let relative = path.strip_prefix(dir).ok()?.to_string_lossy().to_string();
+            let relative = format!("\\{}", relative.trim_start_matches('\\'));
+            let xml = parse_task_xml(&text_bytes_to_string(&fs::read(&path).ok()?));
+
+            Some((relative.to_lowercase(), (relative, xml)))
+        })
+        .collect()
+}
+
+fn read_task_cache() -> HashMap<String, CachedTask> {
+    let mut tasks = HashMap::new();
+    let mut ids = HashSet::new();
+
+    match LOCAL_MACHINE.open(format!("{}\\Tree", TASK_CACHE_KEY)) {
+        Ok(tree) => read_tree(&tree, String::new(), &mut tasks, &mut ids),
+        Err(e) => {
+            if cfg!(dev) {
+                println!("{e:?}");
+            }
+        }
+    }
+
+    read_orphaned_tasks(&mut tasks, &ids);
+
+    tasks
+}
+
+fn read_tree(
+    key: &Key,
+    path: String,
+    tasks: &mut HashMap<String, CachedTask>,
+    ids: &mut HashSet<String>,
+) {
+    // у папок нет Id, у задач он указывает на ключ в TaskCache\Tasks
+    if let Ok(id) = key.get_string("Id") {
+        tasks.insert(
+            path.clone(),
+            CachedTask {
+                tree: true,
+                security_descriptor: key.get_value("SD").is_ok(),
+                actions: read_cached_actions(&id),
+            },
+        );
+
+        ids.insert(id.to_lowercase());
+    }
+
+    let Ok(names) = key.keys() else {
+        return;
+    };
+
+    for name in names {
+        if let Ok(sub) = key.open(&name) {
+            read_tree(&sub, format!("{}\\{}", path, name), tasks, ids);
+        }
+    }
+}
+
+// удаление ключа из Tree прячет задачу, но описание в Tasks остается и она продолжает работать
+fn read_orphaned_tasks(tasks: &mut HashMap<String, CachedTask>, ids: &HashSet<String>) {
+    let Ok(cache) = LOCAL_MACHINE.open(format!("{}\\Tasks", TASK_CACHE_KEY)) else {
+        return;
+    };
+
+    let Ok(names) = cache.keys() else {
+        return;
+    };
+
+    for id in names.filter(|id| !ids.contains(&id.to_lowercase())) {
+        let path = cache
+            .open(&id)
+            .and_then(|key| key.get_string("Path"))
+            .unwrap_or_else(|_| id.clone());
+
+        tasks.entry(path).or_insert_with(|| CachedTask {
+            tree: false,
+            security_descriptor: false,
+            actions: read_cached_actions(&id),
+        });
+    }
+}
+
+// в значении Actions хранится бинарная копия действий: строки с префиксом длины в байтах
+fn read_cached_actions(id: &str) -> String {
+    let Ok(key) = LOCAL_MACHINE.open(format!("{}\\Tasks\\{}", TASK_CACHE_KEY, id)) else {
+        return String::new();
+    };
+
+    let Ok(value) = key.get_value("Actions") else {
+        return String::new();
+    };
+
+    let data = bytes_to_vec_u8(value.bytes());
+    let mut strings = vec![];
+    let mut offset = 0;
+
+    while offset + 4 <= data.len() {
+        let size = u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap()) as usize;
+        let start = offset + 4;
+
+        if size >= 4 && size.is_multiple_of(2) && start + size <= data.len() {
+            let string = utf16_bytes_to_string(&data[start..start + size]);
+
+            if !string.is_empty() && !string.chars().any(|c| c.is_control()) {
+                strings.push(string);
+                offset = start + size;
+                continue;
+            }
+        }
+
+        offset += 2;
+    }
+
+    strings.join(" ")
+}
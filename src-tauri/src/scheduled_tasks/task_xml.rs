@@ -0,0 +1,123 @@
+use std::sync::OnceLock;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::utils::{xml_tag_values, xml_unescape};
+
+static TRIGGER_REGEX: OnceLock<Regex> = OnceLock::new();
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct TaskXml {
+    pub author: String,
+    pub date: String,
+    pub hidden: bool,
+    pub actions: Vec<String>,
+    pub triggers: Vec<String>,
+}
+
+// https://learn.microsoft.com/en-us/windows/win32/taskschd/task-scheduler-schema
+pub fn parse_task_xml(xml: &str) -> TaskXml {
+    let registration_info = first_tag(xml, "RegistrationInfo").unwrap_or_default();
+    let settings = first_tag(xml, "Settings").unwrap_or_default();
+
+    let mut actions = vec![];
+
+    for exec in xml_tag_values(xml, "Exec") {
+        let command = tag_text(&exec, "Command");
+        let arguments = tag_text(&exec, "Arguments");
+
+        actions.push(match arguments.is_empty() {
+            true => command,
+            false => format!("{} {}", command, arguments),
+        });
+    }
+
+    // com-обработчики запускаются по clsid, исполняемый файл ищется в реестре
+    for handler in xml_tag_values(xml, "ComHandler") {
+        actions.push(format!("com: {}", tag_text(&handler, "ClassId")));
+    }
+
+    let triggers = match first_tag(xml, "Triggers") {
+        Some(triggers) => TRIGGER_REGEX
+            .get_or_init(|| Regex::new(r"<(\w+Trigger)\b").unwrap())
+            .captures_iter(&triggers)
+            .map(|c| c[1].to_string())
+            .collect(),
+        None => vec![],
+    };
+
+    TaskXml {
+        author: tag_text(&registration_info, "Author"),
+        date: tag_text(&registration_info, "Date"),
+        hidden: tag_text(&settings, "Hidden").eq_ignore_ascii_case("true"),
+        actions,
+        triggers,
+    }
+}
+
+fn first_tag(xml: &str, tag: &str) -> Option<String> {
+    xml_tag_values(xml, tag).into_iter().next()
+}
+
+fn tag_text(xml: &str, tag: &str) -> String {
+    first_tag(xml, tag)
+        .map(|value| xml_unescape(value.trim()))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_task_xml() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-16"?>
+<Task version="1.2" xmlns="http://schemas.microsoft.com/windows/2004/02/mit/task">
+  <RegistrationInfo>
+    <Date>2024-01-01T10:00:00</Date>
+    <Author>DESKTOP\user</Author>
+  </RegistrationInfo>
+  <Triggers>
+    <LogonTrigger><Enabled>true</Enabled></LogonTrigger>
+    <CalendarTrigger><StartBoundary>2024-01-01T10:00:00</StartBoundary></CalendarTrigger>
+  </Triggers>
+  <Settings>
+    <Hidden>true</Hidden>
+  </Settings>
+  <Actions Context="Author">
+    <Exec>
+      <Command>C:\Users\user\loader.exe</Command>
+      <Arguments>-silent &amp; -inject</Arguments>
+    </Exec>
+    <ComHandler>
+      <ClassId>{0F87369F-A4E5-4CFC-BD3E-73E6154572DD}</ClassId>
+    </ComHandler>
+  </Actions>
+</Task>"#;
+
+        let task = parse_task_xml(xml);
+
+        assert_eq!(task.author, "DESKTOP\\user");
+        assert_eq!(task.date, "2024-01-01T10:00:00");
+        assert!(task.hidden);
+        assert_eq!(
+            task.actions,
+            [
+                "C:\\Users\\user\\loader.exe -silent & -inject",
+                "com: {0F87369F-A4E5-4CFC-BD3E-73E6154572DD}",
+            ]
+        );
+        assert_eq!(task.triggers, ["LogonTrigger", "CalendarTrigger"]);
+    }
+
+    #[test]
+    fn parses_empty_xml() {
+        let task = parse_task_xml("");
+
+        assert!(task.author.is_empty());
+        assert!(!task.hidden);
+        assert!(task.actions.is_empty());
+        assert!(task.triggers.is_empty());
+    }
+}
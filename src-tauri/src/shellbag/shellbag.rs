@@ -1,13 +1,35 @@
-use std::io::Read;
-
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
-use windows_registry::{Type, CURRENT_USER};
 
-use crate::mini_dat::registry_md::bytes_to_vec_u8;
+use crate::{
+    profiles::user_hive::{collect_user_hives, RegKey},
+    regf::hive::REG_BINARY,
+};
+
 use super::shellbag_dat::{BagMRU, ShellBagDat};
 
+const BAG_MRU_KEY: &str = "Local Settings\\Software\\Microsoft\\Windows\\Shell\\BagMRU";
+
 pub fn collect_shell_bag() -> Vec<ShellBagDat> {
-    let items: Vec<ShellBagDat> = read_bag_mru(String::new(), String::new())
+    collect_user_hives()
+        .iter()
+        .flat_map(|user| {
+            let Some(key) = user.open_classes(BAG_MRU_KEY) else {
+                return vec![];
+            };
+
+            collect_user_shell_bag(&key)
+                .into_iter()
+                .map(|mut item| {
+                    item.user = user.profile.name.clone();
+                    item
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn collect_user_shell_bag(root: &RegKey) -> Vec<ShellBagDat> {
+    let items: Vec<ShellBagDat> = read_bag_mru(root, String::new(), String::new())
         .par_iter()
         .flat_map(|bag_mru| {
             let mut vec = Vec::new();
@@ -43,6 +65,7 @@ pub fn collect_shell_bag() -> Vec<ShellBagDat> {
 pub fn bag_mru_to_shell_bag(bag_mru: &BagMRU) -> Option<ShellBagDat> {
     bag_mru.full_name.as_ref().map(|path| {
         ShellBagDat {
+            user: String::new(),
             path: path
                 .replacen("\\\\", "", 1)
                 .replace(":\\\\", ":\\"),
@@ -65,61 +88,44 @@ pub fn collect_sub_shell_bag(bag_mru: &BagMRU) -> Vec<ShellBagDat> {
         .collect()
 }
 
-fn read_bag_mru(start_path: String, before_full_name: String) -> Vec<BagMRU> {
-    let registry_path = format!(
-        "SOFTWARE\\Classes\\Local Settings\\Software\\Microsoft\\Windows\\Shell\\BagMRU\\{}",
-        start_path
-    );
-
-    let key = match CURRENT_USER.open(&registry_path) {
-        Ok(key) => key,
-        Err(e) => {
-            if cfg!(debug_assertions) {
-                eprintln!("Registry open error: {e:?}");
-            }
-            return vec![];
-        }
+fn read_bag_mru(root: &RegKey, start_path: String, before_full_name: String) -> Vec<BagMRU> {
+    let Some(key) = root.open(start_path.trim_start_matches('\\')) else {
+        return vec![];
     };
 
-    match key.values() {
-        Ok(values) => values
-            .into_iter()
-            .filter_map(|(name, value)| {
-                if name.parse::<u32>().is_err() || value.ty() != Type::Bytes {
-                    return None;
-                }
-
-                let entry_bytes = value.bytes();
-
-                let new_path = format!("{}\\{}", start_path, name);
-                let mut bag_mru = BagMRU {
-                    path: new_path.clone(),
-                    entry: bytes_to_vec_u8(entry_bytes),
-                    sub: vec![],
-                    full_name: None,
-                    short_name: None,
-                };
-
-                decode_bag_mru_entry_name(&mut bag_mru);
+    key.values()
+        .into_iter()
+        .filter_map(|value| {
+            if value.name.parse::<u32>().is_err() || value.value_type != REG_BINARY {
+                return None;
+            }
 
-                let full_name = format!(
-                    "{}\\{}",
-                    before_full_name,
-                    bag_mru.short_name.clone().unwrap_or_default()
-                );
-                bag_mru.full_name = Some(full_name);
-                bag_mru.sub = read_bag_mru(new_path, bag_mru.full_name.clone().unwrap_or_default());
+            let new_path = format!("{}\\{}", start_path, value.name);
+            let mut bag_mru = BagMRU {
+                path: new_path.clone(),
+                entry: value.data,
+                sub: vec![],
+                full_name: None,
+                short_name: None,
+            };
 
-                Some(bag_mru)
-            })
-            .collect(),
-        Err(e) => {
-            if cfg!(debug_assertions) {
-                eprintln!("Registry read error: {e:?}");
-            }
-            vec![]
-        }
-    }
+            decode_bag_mru_entry_name(&mut bag_mru);
+
+            let full_name = format!(
+                "{}\\{}",
+                before_full_name,
+                bag_mru.short_name.clone().unwrap_or_default()
+            );
+            bag_mru.full_name = Some(full_name);
+            bag_mru.sub = read_bag_mru(
+                root,
+                new_path,
+                bag_mru.full_name.clone().unwrap_or_default(),
+            );
+
+            Some(bag_mru)
+        })
+        .collect()
 }
 
 pub fn decode_bag_mru_entry_name(bag_mru: &mut BagMRU) {
@@ -159,4 +165,4 @@ pub fn decode_bag_mru_entry_name(bag_mru: &mut BagMRU) {
         }
         _ => {}
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,188 @@
+use std::{fs, path::Path};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    profiles::profiles::collect_user_profiles,
+    utils::{copy_sqlite_to_temp, remove_sqlite_temp},
+};
+
+const TIMELINE_DIR: &str = "ConnectedDevicesPlatform";
+const ACTIVITIES_CACHE: &str = "ActivitiesCache.db";
+
+const TIMELINE_TABLES: [&str; 2] = ["Activity", "ActivityOperation"];
+
+// платформы в AppId, по которым можно узнать путь к приложению
+const APP_PLATFORMS: [&str; 3] = ["x_exe_path", "windows_win32", "packageId"];
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TimelineActivity {
+    pub user: String,
+    pub source: String,
+    pub app: String,
+    pub document: String,
+    pub activity_type: i64,
+    pub start: i64,
+    pub end: i64,
+    pub duration: i64,
+}
+
+pub fn collect_timeline() -> Vec<TimelineActivity> {
+    collect_user_profiles()
+        .iter()
+        .flat_map(|profile| {
+            let Ok(dirs) = fs::read_dir(profile.local_app_data().join(TIMELINE_DIR)) else {
+                return vec![];
+            };
+
+            dirs.filter_map(Result::ok)
+                .map(|dir| dir.path().join(ACTIVITIES_CACHE))
+                .filter(|path| path.exists())
+                .flat_map(|path| read_activities_cache(&path))
+                .map(|mut activity| {
+                    activity.user = profile.name.clone();
+                    activity
+                })
+                .collect()
+        })
+        .collect()
+}
+
+pub fn read_activities_cache(path: &Path) -> Vec<TimelineActivity> {
+    let Some(temporary) = copy_sqlite_to_temp(path) else {
+        return vec![];
+    };
+
+    let mut response = vec![];
+
+    match sqlite::open(&temporary) {
+        Ok(connection) => {
+            for table in TIMELINE_TABLES {
+                let query = format!(
+                    "SELECT AppId, ActivityType, Payload, StartTime, EndTime FROM {}",
+                    table
+                );
+
+                let statement = match connection.prepare(query) {
+                    Ok(statement) => statement,
+                    Err(e) => {
+                        if cfg!(dev) {
+                            println!("{e:?}");
+                        }
+
+                        continue;
+                    }
+                };
+
+                for row in statement.into_iter().filter_map(Result::ok) {
+                    let app_id = row.try_read::<Option<&str>, _>(0).ok().flatten();
+                    let payload = row.try_read::<Option<&[u8]>, _>(2).ok().flatten();
+
+                    response.push(parse_activity(
+                        table,
+                        app_id.unwrap_or_default(),
+                        payload.unwrap_or_default(),
+                        row.try_read::<i64, _>(1).unwrap_or(0),
+                        row.try_read::<i64, _>(3).unwrap_or(0),
+                        row.try_read::<i64, _>(4).unwrap_or(0),
+                    ));
+                }
+            }
+        }
+
+        Err(e) => {
+            if cfg!(dev) {
+                println!("{e:?}");
+            }
+        }
+    }
+
+    remove_sqlite_temp(&temporary);
+
+    response
+}
+
+pub fn parse_activity(
+    source: &str,
+    app_id: &str,
+    payload: &[u8],
+    activity_type: i64,
+    start: i64,
+    end: i64,
+) -> TimelineActivity {
+    let payload: Value = serde_json::from_slice(payload).unwrap_or(Value::Null);
+
+    let text = |name: &str| {
+        payload
+            .get(name)
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string()
+    };
+
+    let mut document = text("description");
+
+    if document.is_empty() {
+        document = text("displayText");
+    }
+
+    let mut app = parse_app_id(app_id);
+
+    if app.is_empty() {
+        app = text("appDisplayName");
+    }
+
+    // время в фокусе есть только у записей об использовании приложения
+    let duration = payload
+        .get("activeDurationSeconds")
+        .and_then(Value::as_i64)
+        .unwrap_or(if end > start { end - start } else { 0 });
+
+    TimelineActivity {
+        user: String::new(),
+        source: source.to_string(),
+        app,
+        document,
+        activity_type,
+        start,
+        end,
+        duration,
+    }
+}
+
+// [{"application":"C:\\path\\app.exe","platform":"x_exe_path"}, ...]
+fn parse_app_id(app_id: &str) -> String {
+    let Ok(Value::Array(entries)) = serde_json::from_str::<Value>(app_id) else {
+        return String::new();
+    };
+
+    let application = |platform: &str| {
+        entries.iter().find_map(|entry| {
+            (entry.get("platform")?.as_str()? == platform)
+                .then(|| entry.get("application")?.as_str())
+                .flatten()
+        })
+    };
+
+    APP_PLATFORMS
+        .iter()
+        .find_map(|platform| application(platform))
+        .or_else(|| {
+            entries
+                .first()
+                .and_then(|entry| entry.get("application")?.as_str())
+        })
+        .unwrap_or_default()
+        .to_string()
+}
+
+pub fn activity_type_name(activity_type: i64) -> &'static str {
+    match activity_type {
+        5 => "открытие",
+        6 => "использование",
+        10 => "буфер обмена",
+        16 => "копирование",
+        _ => "неизвестно",
+    }
+}
@@ -1,28 +1,36 @@
 use std::{
+    collections::HashMap,
+    env,
     ffi::OsString,
-    fs::File,
-    io::Write,
+    fs::{self, File},
+    io::{BufReader, Read, Write},
     os::windows::{ffi::OsStringExt, process::CommandExt},
-    path::PathBuf,
+    path::{Path, PathBuf},
     process::Command,
-    ptr::{addr_of_mut, null_mut}
+    ptr::{addr_of_mut, null_mut},
+    sync::{Mutex, OnceLock},
 };
 
+use chrono::DateTime;
 use jwalk::WalkDir;
 use rand::{distr::Alphanumeric, Rng};
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use tauri::{AppHandle, Manager};
 use windows::{
     core::{GUID, PCWSTR, PWSTR},
     Win32::{
-        Foundation::{CloseHandle, HANDLE},
+        Foundation::{CloseHandle, FILETIME, HANDLE},
         Security::{
             Authorization::ConvertSidToStringSidW, GetTokenInformation, TokenUser, TOKEN_QUERY,
             TOKEN_USER,
         },
-        System::Threading::{GetCurrentProcess, OpenProcessToken},
+        System::{
+            Registry::{RegCloseKey, RegOpenKeyExW, RegQueryInfoKeyW, HKEY, KEY_READ},
+            Threading::{GetCurrentProcess, OpenProcessToken},
+        },
         UI::Shell::{SHGetKnownFolderPath, KNOWN_FOLDER_FLAG},
     },
 };
@@ -50,6 +58,315 @@ pub fn random_name() -> String {
     s
 }
 
+// базы sqlite, открытые системными службами, читаем через копию вместе с журналом wal
+pub fn copy_sqlite_to_temp(path: &Path) -> Option<PathBuf> {
+    let temporary = env::temp_dir().join(random_name());
+
+    if let Err(e) = fs::copy(path, &temporary) {
+        if cfg!(dev) {
+            println!("{e:?}");
+        }
+
+        return None;
+    }
+
+    let wal = PathBuf::from(format!("{}-wal", path.to_string_lossy()));
+
+    if wal.exists() {
+        let _ = fs::copy(&wal, format!("{}-wal", temporary.to_string_lossy()));
+    }
+
+    Some(temporary)
+}
+
+// файлы, открытые службами без общего доступа (SRUDB.dat и т.п.), копируем через теневую копию тома
+pub fn copy_locked_file_to_temp(path: &Path) -> Option<PathBuf> {
+    let temporary = env::temp_dir().join(random_name());
+
+    if fs::copy(path, &temporary).is_ok() {
+        return Some(temporary);
+    }
+
+    let status = Command::new("esentutl.exe")
+        .arg("/y")
+        .arg(path)
+        .arg("/vss")
+        .arg("/d")
+        .arg(&temporary)
+        .creation_flags(0x08000000)
+        .output();
+
+    match status {
+        Ok(output) if output.status.success() && temporary.is_file() => Some(temporary),
+        Ok(output) => {
+            if cfg!(dev) {
+                println!("{}", String::from_utf8_lossy(&output.stdout));
+            }
+
+            let _ = fs::remove_file(&temporary);
+            None
+        }
+        Err(e) => {
+            if cfg!(dev) {
+                println!("{e:?}");
+            }
+
+            None
+        }
+    }
+}
+
+pub fn remove_sqlite_temp(temporary: &Path) {
+    let _ = fs::remove_file(temporary);
+    let _ = fs::remove_file(format!("{}-wal", temporary.to_string_lossy()));
+}
+
+// количество 100нс интервалов между 1601-01-01 и 1970-01-01
+const FILETIME_UNIX_EPOCH: i64 = 116_444_736_000_000_000;
+
+pub fn unix_to_string(seconds: i64) -> String {
+    match DateTime::from_timestamp(seconds, 0) {
+        Some(time) => time.format("%Y-%m-%d %H:%M:%S").to_string(),
+        None => String::from("undefined"),
+    }
+}
+
+pub fn filetime_to_unix(filetime: i64) -> i64 {
+    (filetime - FILETIME_UNIX_EPOCH) / 10_000_000
+}
+
+pub fn unix_to_filetime(unix: i64) -> i64 {
+    unix * 10_000_000 + FILETIME_UNIX_EPOCH
+}
+
+pub fn filetime_to_string(filetime: i64) -> String {
+    if filetime <= 0 {
+        return String::from("undefined");
+    }
+
+    unix_to_string(filetime_to_unix(filetime))
+}
+
+// ole automation date: дни с 1899-12-30
+pub fn ole_date_to_string(date: f64) -> String {
+    unix_to_string(((date - 25569.0) * 86400.0) as i64)
+}
+
+pub fn utf16_bytes_to_string(bytes: &[u8]) -> String {
+    let utf16: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|chunk| u16::from_le_bytes([chunk[0], chunk[1]]))
+        .collect();
+
+    String::from_utf16_lossy(&utf16)
+        .trim_end_matches('\0')
+        .to_string()
+}
+
+// текстовые файлы windows обычно в utf-16 с bom, остальные читаем как utf-8
+pub fn text_bytes_to_string(bytes: &[u8]) -> String {
+    if let Some(bytes) = bytes.strip_prefix(&[0xff, 0xfe]) {
+        return utf16_bytes_to_string(bytes);
+    }
+
+    // utf-16 без bom, например файлы pca
+    if bytes.len() >= 2 && bytes[0] != 0 && bytes[1] == 0 {
+        return utf16_bytes_to_string(bytes);
+    }
+
+    let bytes = bytes.strip_prefix(&[0xef, 0xbb, 0xbf]).unwrap_or(bytes);
+    String::from_utf8_lossy(bytes).to_string()
+}
+
+// выражения для тегов собираются один раз, функция вызывается на каждый файл задачи
+static XML_TAG_REGEXES: OnceLock<Mutex<HashMap<String, Regex>>> = OnceLock::new();
+
+pub fn xml_tag_values(xml: &str, tag: &str) -> Vec<String> {
+    let regex = XML_TAG_REGEXES
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .entry(tag.to_string())
+        .or_insert_with(|| {
+            Regex::new(&format!(
+                r"(?s)<{0}(?:\s[^>]*)?>(.*?)</{0}>",
+                regex::escape(tag)
+            ))
+            .unwrap()
+        })
+        .clone();
+
+    regex
+        .captures_iter(xml)
+        .map(|c| c[1].to_string())
+        .collect()
+}
+
+pub fn xml_unescape(value: &str) -> String {
+    value
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+// C:\..., \\server\share\...
+pub fn is_windows_path(value: &str) -> bool {
+    let bytes = value.as_bytes();
+
+    value.starts_with("\\\\")
+        || (bytes.len() > 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':')
+}
+
+pub fn sid_bytes_to_string(bytes: &[u8]) -> Option<String> {
+    if bytes.len() < 8 {
+        return None;
+    }
+
+    let count = bytes[1] as usize;
+    if bytes.len() < 8 + count * 4 {
+        return None;
+    }
+
+    let authority = bytes[2..8]
+        .iter()
+        .fold(0u64, |acc, b| (acc << 8) | *b as u64);
+
+    let mut sid = format!("S-{}-{}", bytes[0], authority);
+    for i in 0..count {
+        let offset = 8 + i * 4;
+        let sub = u32::from_le_bytes(bytes[offset..offset + 4].try_into().ok()?);
+        sid.push_str(&format!("-{}", sub));
+    }
+
+    Some(sid)
+}
+
+pub fn guid_bytes_to_string(guid: &[u8; 16]) -> String {
+    format!(
+        "{{{:08X}-{:04X}-{:04X}-{}-{}}}",
+        u32::from_le_bytes([guid[0], guid[1], guid[2], guid[3]]),
+        u16::from_le_bytes([guid[4], guid[5]]),
+        u16::from_le_bytes([guid[6], guid[7]]),
+        hex::encode_upper(&guid[8..10]),
+        hex::encode_upper(&guid[10..16])
+    )
+}
+
+const EXECUTABLE_EXTENSIONS: [&str; 10] = [
+    ".exe", ".dll", ".sys", ".com", ".scr", ".bat", ".cmd", ".ps1", ".vbs", ".js",
+];
+
+pub fn get_windows_dir() -> String {
+    env::var("SystemRoot").unwrap_or_else(|_| String::from("C:\\Windows"))
+}
+
+// раскрывает переменные вида %SystemRoot%, неизвестные оставляет как есть
+pub fn expand_environment_strings(value: &str) -> String {
+    let mut response = String::new();
+    let mut rest = value;
+
+    while let Some(start) = rest.find('%') {
+        let Some(end) = rest[start + 1..].find('%') else {
+            break;
+        };
+
+        let name = &rest[start + 1..start + 1 + end];
+        response.push_str(&rest[..start]);
+
+        match env::var(name) {
+            Ok(value) if !name.is_empty() => response.push_str(&value),
+            _ => response.push_str(&rest[start..start + end + 2]),
+        }
+
+        rest = &rest[start + end + 2..];
+    }
+
+    response.push_str(rest);
+    response
+}
+
+// достает путь к исполняемому файлу из командной строки вроде тех, что лежат в Run или ImagePath
+pub fn resolve_executable_path(command: &str) -> String {
+    let command = expand_environment_strings(command.trim());
+    let windows_dir = get_windows_dir();
+
+    let path = match command.strip_prefix('"') {
+        Some(rest) => rest.split('"').next().unwrap_or(rest).to_string(),
+        None => {
+            let mut candidate = String::new();
+            let mut found = None;
+
+            // путь без кавычек может содержать пробелы, наращиваем его по словам
+            for part in command.split(' ') {
+                if !candidate.is_empty() {
+                    candidate.push(' ');
+                }
+                candidate.push_str(part);
+
+                let lower = candidate.to_lowercase();
+                if Path::new(&candidate).is_file()
+                    || EXECUTABLE_EXTENSIONS.iter().any(|ext| lower.ends_with(ext))
+                {
+                    found = Some(candidate.clone());
+                    break;
+                }
+            }
+
+            found.unwrap_or_else(|| command.split(' ').next().unwrap_or("").to_string())
+        }
+    };
+
+    let path = path.trim_start_matches("\\??\\");
+    let lower = path.to_lowercase();
+
+    if lower.starts_with("\\systemroot\\") {
+        return format!("{}{}", windows_dir, &path["\\SystemRoot".len()..]);
+    }
+
+    if lower.starts_with("system32\\") || lower.starts_with("syswow64\\") {
+        return format!("{}\\{}", windows_dir, path);
+    }
+
+    // голое имя файла ищем там же, где его нашла бы система
+    if !path.contains('\\') && !path.is_empty() {
+        for dir in [format!("{}\\System32", windows_dir), windows_dir.clone()] {
+            let candidate = Path::new(&dir).join(path);
+
+            if candidate.is_file() {
+                return candidate.to_string_lossy().to_string();
+            }
+
+            if !lower.contains('.') {
+                let candidate = Path::new(&dir).join(format!("{}.exe", path));
+                if candidate.is_file() {
+                    return candidate.to_string_lossy().to_string();
+                }
+            }
+        }
+    }
+
+    path.to_string()
+}
+
+pub fn sha256_file(path: &str) -> Option<String> {
+    let file = File::open(path).ok()?;
+    let mut reader = BufReader::new(file);
+    let mut hasher = Sha256::new();
+    let mut buffer = [0u8; 8192];
+
+    loop {
+        let bytes_read = reader.read(&mut buffer).ok()?;
+        if bytes_read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..bytes_read]);
+    }
+
+    Some(hex::encode(hasher.finalize()))
+}
+
 pub fn rot13(text: &str) -> String {
     text.chars()
         .map(|c| match c {
@@ -260,6 +577,42 @@ pub fn get_current_username_in_sid() -> Option<String> {
     }
 }
 
+// windows-registry не отдает время последней записи ключа, поэтому читаем его напрямую
+pub fn get_key_last_write_time(root: HKEY, path: &str) -> i64 {
+    unsafe {
+        let mut key = HKEY::default();
+        let path: Vec<u16> = path.encode_utf16().chain(Some(0)).collect();
+
+        if RegOpenKeyExW(root, PCWSTR(path.as_ptr()), Some(0), KEY_READ, &mut key).is_err() {
+            return 0;
+        }
+
+        let mut last_write = FILETIME::default();
+        let result = RegQueryInfoKeyW(
+            key,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(addr_of_mut!(last_write)),
+        );
+
+        let _ = RegCloseKey(key);
+
+        if result.is_err() {
+            return 0;
+        }
+
+        ((last_write.dwHighDateTime as i64) << 32) | last_write.dwLowDateTime as i64
+    }
+}
+
 pub async fn reqwest_raw_to_t<T>(url: String) -> Option<T>
 where
     T: for<'a> Deserialize<'a> + Serialize,
@@ -0,0 +1,330 @@
+use std::{collections::HashMap, fs, path::Path};
+
+use jwalk::WalkDir;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    profiles::profiles::collect_user_profiles,
+    utils::{get_windows_dir, text_bytes_to_string},
+};
+
+const WER_DIRS: [&str; 2] = [
+    "C:\\ProgramData\\Microsoft\\Windows\\WER\\ReportArchive",
+    "C:\\ProgramData\\Microsoft\\Windows\\WER\\ReportQueue",
+];
+
+const USER_WER_DIRS: [&str; 2] = [
+    "Microsoft\\Windows\\WER\\ReportArchive",
+    "Microsoft\\Windows\\WER\\ReportQueue",
+];
+
+const WATCHED_APPS: [&str; 2] = ["cs2.exe", "steam.exe"];
+
+// в APPCRASH и BEX сигнатуры идут в фиксированном порядке,
+// а их названия могут быть локализованы
+const FAULT_MODULE_SIG: usize = 3;
+const APPCRASH_EXCEPTION_CODE_SIG: usize = 6;
+// в BEX на месте кода исключения записано его смещение, а код идет следующим
+const BEX_EXCEPTION_CODE_SIG: usize = 7;
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WerReport {
+    pub user: String,
+    pub path: String,
+    pub event_type: String,
+    pub timestamp: i64,
+    pub app: String,
+    pub app_path: String,
+    pub fault_module: String,
+    pub exception_code: String,
+    pub unknown_modules: Vec<String>,
+}
+
+pub fn collect_wer_reports() -> Vec<WerReport> {
+    // отчеты в ProgramData общие для системы, в профилях принадлежат пользователю
+    let mut dirs: Vec<(String, String)> = WER_DIRS
+        .iter()
+        .map(|dir| (String::new(), dir.to_string()))
+        .collect();
+
+    for profile in collect_user_profiles() {
+        for dir in USER_WER_DIRS {
+            dirs.push((
+                profile.name.clone(),
+                profile
+                    .local_app_data()
+                    .join(dir)
+                    .to_string_lossy()
+                    .to_string(),
+            ));
+        }
+    }
+
+    dirs.iter()
+        .flat_map(|(user, dir)| {
+            WalkDir::new(dir)
+                .into_iter()
+                .filter_map(Result::ok)
+                .filter(|entry| entry.file_type.is_file())
+                .filter(|entry| {
+                    entry
+                        .file_name
+                        .to_string_lossy()
+                        .to_lowercase()
+                        .ends_with(".wer")
+                })
+                .filter_map(|entry| read_wer_report(&entry.path()))
+                .map(|mut report| {
+                    report.user = user.clone();
+                    report
+                })
+                .collect::<Vec<WerReport>>()
+        })
+        .filter(|report| {
+            WATCHED_APPS
+                .iter()
+                .any(|app| report.app.eq_ignore_ascii_case(app))
+        })
+        .collect()
+}
+
+pub fn read_wer_report(path: &Path) -> Option<WerReport> {
+    let data = match fs::read(path) {
+        Ok(data) => data,
+        Err(e) => {
+            if cfg!(dev) {
+                println!("{e:?}");
+            }
+
+            return None;
+        }
+    };
+
+    let mut report = parse_wer_report(&text_bytes_to_string(&data))?;
+    report.path = path.to_string_lossy().to_string();
+
+    Some(report)
+}
+
+pub fn parse_wer_report(text: &str) -> Option<WerReport> {
+    let mut fields: HashMap<String, String> = HashMap::new();
+    let mut signatures: HashMap<usize, (String, String)> = HashMap::new();
+    let mut modules: Vec<String> = vec![];
+
+    for line in text.lines() {
+        let Some((name, value)) = line.trim().split_once('=') else {
+            continue;
+        };
+
+        if name.starts_with("LoadedModule[") {
+            modules.push(value.to_string());
+        } else if let Some(sig) = name.strip_prefix("Sig[") {
+            let Some((index, kind)) = sig.split_once("].") else {
+                continue;
+            };
+
+            let Ok(index) = index.parse::<usize>() else {
+                continue;
+            };
+
+            let signature = signatures.entry(index).or_default();
+
+            match kind {
+                "Name" => signature.0 = value.to_string(),
+                "Value" => signature.1 = value.to_string(),
+                _ => {}
+            }
+        } else {
+            fields.insert(name.to_string(), value.to_string());
+        }
+    }
+
+    let event_type = fields.get("EventType")?.clone();
+    let app_path = fields.get("AppPath").cloned().unwrap_or_default();
+
+    let app = fields
+        .get("NsAppName")
+        .cloned()
+        .or_else(|| {
+            app_path
+                .rsplit('\\')
+                .next()
+                .filter(|name| !name.is_empty())
+                .map(|name| name.to_string())
+        })
+        .or_else(|| signatures.get(&0).map(|sig| sig.1.clone()))
+        .unwrap_or_default();
+
+    let upper_event_type = event_type.to_uppercase();
+    let exception_code_sig = if upper_event_type.starts_with("APPCRASH") {
+        Some(APPCRASH_EXCEPTION_CODE_SIG)
+    } else if upper_event_type.starts_with("BEX") {
+        Some(BEX_EXCEPTION_CODE_SIG)
+    } else {
+        None
+    };
+
+    let signature = |name: &str, index: Option<usize>| {
+        signatures
+            .values()
+            .find(|sig| sig.0.eq_ignore_ascii_case(name))
+            .or_else(|| index.and_then(|index| signatures.get(&index)))
+            .map(|sig| sig.1.clone())
+            .unwrap_or_default()
+    };
+
+    // модуль с ошибкой стоит на одном месте в обоих типах отчетов
+    let fault_module_sig = exception_code_sig.is_some().then_some(FAULT_MODULE_SIG);
+
+    let fault_module = signature("Fault Module Name", fault_module_sig);
+    let exception_code = signature("Exception Code", exception_code_sig);
+
+    let unknown_modules = modules
+        .into_iter()
+        .filter(|module| !is_known_module(module, &app_path))
+        .collect();
+
+    Some(WerReport {
+        user: String::new(),
+        path: String::new(),
+        event_type,
+        timestamp: fields
+            .get("EventTime")
+            .and_then(|time| time.parse::<i64>().ok())
+            .unwrap_or(0),
+        app,
+        app_path,
+        fault_module,
+        exception_code,
+        unknown_modules,
+    })
+}
+
+// известными считаем системные модули и модули из папки игры,
+// в Program Files может лежать что угодно, поэтому она не исключается
+fn is_known_module(module: &str, app_path: &str) -> bool {
+    let module = module.to_lowercase();
+
+    // без разделителя под префикс попала бы и папка вида C:\Windows2
+    let mut known_dirs = vec![format!("{}\\", get_windows_dir().to_lowercase())];
+
+    if let Some(root) = app_root(&app_path.to_lowercase()) {
+        known_dirs.push(root);
+    }
+
+    known_dirs
+        .iter()
+        .filter(|dir| !dir.is_empty())
+        .any(|dir| module.starts_with(dir.as_str()))
+}
+
+// для игр steam корнем считаем папку steamapps\common\<игра>
+fn app_root(app_path: &str) -> Option<String> {
+    const STEAM_COMMON: &str = "\\steamapps\\common\\";
+
+    if let Some(index) = app_path.find(STEAM_COMMON) {
+        let start = index + STEAM_COMMON.len();
+        let end = app_path[start..]
+            .find('\\')
+            .map(|end| start + end + 1)
+            .unwrap_or(app_path.len());
+
+        return Some(app_path[..end].to_string());
+    }
+
+    app_path
+        .rfind('\\')
+        .map(|index| app_path[..index + 1].to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REPORT: &str = "Version=1\r\n\
+        EventType=APPCRASH\r\n\
+        EventTime=133500000000000000\r\n\
+        Sig[0].Name=Имя приложения\r\n\
+        Sig[0].Value=cs2.exe\r\n\
+        Sig[3].Name=Имя модуля с ошибкой\r\n\
+        Sig[3].Value=cheat.dll\r\n\
+        Sig[6].Name=Код исключения\r\n\
+        Sig[6].Value=c0000005\r\n\
+        LoadedModule[0]=D:\\SteamLibrary\\steamapps\\common\\Counter-Strike Global Offensive\\game\\bin\\win64\\cs2.exe\r\n\
+        LoadedModule[1]=C:\\Windows\\SYSTEM32\\ntdll.dll\r\n\
+        LoadedModule[2]=D:\\SteamLibrary\\steamapps\\common\\Counter-Strike Global Offensive\\game\\csgo\\bin\\win64\\client.dll\r\n\
+        LoadedModule[3]=C:\\Windows2\\cheat.dll\r\n\
+        LoadedModule[4]=C:\\Users\\user\\AppData\\Local\\Temp\\cheat.dll\r\n\
+        AppPath=D:\\SteamLibrary\\steamapps\\common\\Counter-Strike Global Offensive\\game\\bin\\win64\\cs2.exe\r\n";
+
+    #[test]
+    fn parses_localized_appcrash() {
+        let report = parse_wer_report(REPORT).unwrap();
+
+        assert_eq!(report.event_type, "APPCRASH");
+        assert_eq!(report.timestamp, 133500000000000000);
+        assert_eq!(report.app, "cs2.exe");
+        assert_eq!(report.fault_module, "cheat.dll");
+        assert_eq!(report.exception_code, "c0000005");
+        assert_eq!(
+            report.unknown_modules,
+            [
+                "C:\\Windows2\\cheat.dll",
+                "C:\\Users\\user\\AppData\\Local\\Temp\\cheat.dll",
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_localized_bex() {
+        let report = parse_wer_report(
+            "EventType=BEX64\n\
+             Sig[0].Name=Имя приложения\n\
+             Sig[0].Value=cs2.exe\n\
+             Sig[3].Name=Имя модуля с ошибкой\n\
+             Sig[3].Value=cheat.dll\n\
+             Sig[6].Name=Смещение исключения\n\
+             Sig[6].Value=000000000001a2b3\n\
+             Sig[7].Name=Код исключения\n\
+             Sig[7].Value=c0000409\n",
+        )
+        .unwrap();
+
+        assert_eq!(report.fault_module, "cheat.dll");
+        assert_eq!(report.exception_code, "c0000409");
+    }
+
+    #[test]
+    fn reads_named_signatures() {
+        let report = parse_wer_report(
+            "EventType=BlueScreen\n\
+             Sig[0].Name=Fault Module Name\n\
+             Sig[0].Value=driver.sys\n\
+             Sig[1].Name=Exception Code\n\
+             Sig[1].Value=c0000005\n\
+             Sig[3].Value=other.sys\n",
+        )
+        .unwrap();
+
+        assert_eq!(report.fault_module, "driver.sys");
+        assert_eq!(report.exception_code, "c0000005");
+        assert!(report.app_path.is_empty());
+    }
+
+    #[test]
+    fn requires_event_type() {
+        assert!(parse_wer_report("AppPath=C:\\cs2.exe").is_none());
+    }
+
+    #[test]
+    fn finds_app_root() {
+        assert_eq!(
+            app_root("d:\\steamapps\\common\\game\\bin\\game.exe").as_deref(),
+            Some("d:\\steamapps\\common\\game\\")
+        );
+        assert_eq!(
+            app_root("c:\\tools\\app.exe").as_deref(),
+            Some("c:\\tools\\")
+        );
+    }
+}
@@ -1,3 +1,4 @@
 export const GITHUB_URL = "https://github.com/VoidGodAzathot/cshunter";
 export const GITHUB_PACKAGE_URL =
   "https://raw.githubusercontent.com/VoidGodAzathot/cshunter/refs/heads/main/package.json";
+export const LOLDRIVERS_URL = "https://www.loldrivers.io/api/drivers.json";
@@ -1,6 +1,7 @@
 import useStorage from "../hooks/storage";
-import { GITHUB_PACKAGE_URL } from "./consts";
+import { GITHUB_PACKAGE_URL, LOLDRIVERS_URL } from "./consts";
 import {
+  AnalyzeContext,
   Browser,
   CacheDat,
   DownloadDat,
@@ -141,6 +142,53 @@ export const Tasks: Task[] = [
       await set<ShellBagDat[]>("shellbag", shellbag);
     },
   },
+  {
+    name: "Поиск уязвимых драйверов",
+    id: "scan_vulnerable_drivers",
+    cancellable: false,
+    worker: async () => {
+      const [set, get] = useStorage();
+      const current: MiniDat[] | null = await get<MiniDat[]>("mini_dat");
+      const drivers: MiniDat[] = await invoke("scan_vulnerable_drivers", {
+        url: LOLDRIVERS_URL,
+      });
+      await set<MiniDat[]>("mini_dat", [...(current ?? []), ...drivers]);
+    },
+  },
+  {
+    name: "Получение карантина Защитника Windows",
+    id: "get_quarantine_context",
+    cancellable: false,
+    worker: async () => {
+      const [set, get] = useStorage();
+      const current: AnalyzeContext | null = await get<AnalyzeContext>(
+        "analyzer_context"
+      );
+      const context: AnalyzeContext | undefined = await invoke(
+        "get_quarantine_context"
+      );
+      await set<AnalyzeContext>("analyzer_context", {
+        items: [...(current?.items ?? []), ...(context?.items ?? [])],
+      });
+    },
+  },
+  {
+    name: "Получение содержимого корзины",
+    id: "get_recycle_bin_context",
+    cancellable: false,
+    worker: async () => {
+      const [set, get] = useStorage();
+      const current: AnalyzeContext | null = await get<AnalyzeContext>(
+        "analyzer_context"
+      );
+      const context: AnalyzeContext | undefined = await invoke(
+        "get_recycle_bin_context"
+      );
+      await set<AnalyzeContext>("analyzer_context", {
+        items: [...(current?.items ?? []), ...(context?.items ?? [])],
+      });
+    },
+  },
   {
     name: "Дамп строк модулей процесса игры",
     id: "create_dump_modules_strings",